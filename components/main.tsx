@@ -23,11 +23,13 @@ export default function Main() {
   const [isReflective, setIsReflection] = useState<boolean>(true);
   const [isShadow, setIsShadow] = useState<boolean>(true);
   const [cameraSpeed, setCameraSpeed] = useState<number>(3);
+  const [antiAliasing, setAntiAliasing] = useState<number>(1);
 
   useEffect(() => {
       const run = async () => {
           const WASM = await import("wasm");
-          const raytracer = new WASM.Raytracer(pixels, pixels, sphereNumber, isDiffuse, isSpecular, isShadow, isReflective, cameraSpeed);
+          const raytracer = new WASM.Raytracer(pixels, pixels, sphereNumber, isDiffuse, isSpecular, isShadow, isReflective, cameraSpeed, 0xffffff, 0xffffff, 0xffffff);
+          raytracer.set_anti_aliasing(antiAliasing > 1 ? WASM.AntiAliasing.RotatedGrid : WASM.AntiAliasing.None, antiAliasing);
           if (canvasRef.current){
               Engine.create(raytracer, canvasRef.current, pixels, pixels, setFps, setInputInfo);
           }
@@ -36,7 +38,7 @@ export default function Main() {
       return () => {
           Engine.destroy();
       }
-  }, [sphereNumber, pixels, isDiffuse, isSpecular, isShadow, isReflective, cameraSpeed]);
+  }, [sphereNumber, pixels, isDiffuse, isSpecular, isShadow, isReflective, cameraSpeed, antiAliasing]);
 
 
   return (
@@ -102,6 +104,18 @@ export default function Main() {
                       max={1000}
                   />
               </div>
+              <div>
+                  <span>Anti-aliasing samples per side</span>
+                  <Slider
+                      value={antiAliasing}
+                      onChange={(event, value) => setAntiAliasing(value as number)}
+                      marks
+                      valueLabelDisplay="auto"
+                      min={1}
+                      step={1}
+                      max={4}
+                  />
+              </div>
               <div>
                 <span>Number of spheres</span>
                 <Slider
@@ -0,0 +1,80 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::math::vec::Vec3;
+
+// highest pitch reachable by turning, just below looking straight up
+const MAX_PITCH: f64 = FRAC_PI_2 - 0.01;
+
+#[derive(Debug, Copy, Clone)]
+/// A camera at a position with an orientation, every angles being in radians:
+/// * yaw turning right around the vertical axis, 0.0 looking along +Z
+/// * pitch looking up from the horizon
+/// * roll tilting the view around the view direction
+pub struct Camera {
+    pub position: Vec3,
+    pub yaw: f64,
+    pub pitch: f64,
+    pub roll: f64,
+}
+
+impl Camera {
+    /// Creates a new camera looking along +Z
+    pub fn new(position: Vec3) -> Self {
+        return Camera {
+            position,
+            yaw: 0.0,
+            pitch: 0.0,
+            roll: 0.0,
+        };
+    }
+
+    /// Creates a new camera at the position looking at the target,
+    /// rolled to keep `up` upward on the screen
+    pub fn look_at(position: Vec3, target: Vec3, up: Vec3) -> Self {
+        let mut camera = Camera::new(position);
+        let forward = (target - position).normalize();
+        camera.yaw = forward.x.atan2(forward.z);
+        camera.pitch = forward.y.clamp(-1.0, 1.0).asin();
+        // angle of the up vector seen from the camera without roll
+        let (right, camera_up, _) = camera.basis();
+        let up = up - forward * up.dot(forward);
+        if up.norm() > 0.0 {
+            camera.roll = (-up.dot(right)).atan2(up.dot(camera_up));
+        }
+        return camera;
+    }
+
+    /// Get the unit right, up and forward directions of the camera in the scene
+    pub fn basis(&self) -> (Vec3, Vec3, Vec3) {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let forward = Vec3::new(sin_yaw * cos_pitch, sin_pitch, cos_yaw * cos_pitch);
+        // from the yaw only, to stay defined when looking straight up or down
+        let right = Vec3::new(cos_yaw, 0.0, -sin_yaw);
+        let up = forward.cross(right);
+        let (sin_roll, cos_roll) = self.roll.sin_cos();
+        return (
+            right * cos_roll + up * sin_roll,
+            up * cos_roll - right * sin_roll,
+            forward,
+        );
+    }
+
+    /// Express a direction of the camera space, X right, Y up and Z forward, in the scene
+    pub fn to_world(&self, direction: Vec3) -> Vec3 {
+        let (right, up, forward) = self.basis();
+        return right * direction.x + up * direction.y + forward * direction.z;
+    }
+
+    /// Turn the camera by the angles in radians, the pitch being kept
+    /// between straight down and straight up to never flip the view
+    pub fn turn(&mut self, yaw: f64, pitch: f64) {
+        self.yaw = (self.yaw + yaw) % (2.0 * PI);
+        self.pitch = (self.pitch + pitch).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Move the camera by an offset of the camera space, X right, Y up and Z forward
+    pub fn move_relative(&mut self, offset: Vec3) {
+        self.position = self.position + self.to_world(offset);
+    }
+}
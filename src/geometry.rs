@@ -1,3 +1,8 @@
+pub mod aabb;
+pub mod bvh;
 pub mod light;
+pub mod mesh;
 pub mod plane;
+pub mod shape;
 pub mod sphere;
+pub mod triangle;
@@ -0,0 +1,71 @@
+use crate::math::vec::Vec3;
+
+#[derive(Debug, Copy, Clone)]
+/// Axis aligned bounding box
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+    /// Creates an empty box, growing it by any point gives a box around the point
+    pub fn empty() -> Self {
+        let infinity = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        Aabb {
+            min: infinity,
+            max: -infinity,
+        }
+    }
+    /// Grow the box to enclose the point
+    pub fn grow(self, point: Vec3) -> Aabb {
+        return Aabb::new(self.min.min(point), self.max.max(point));
+    }
+    /// Get the smallest box enclosing both boxes
+    pub fn union(self, other: Aabb) -> Aabb {
+        return Aabb::new(self.min.min(other.min), self.max.max(other.max));
+    }
+    /// Get the center of the box
+    pub fn center(&self) -> Vec3 {
+        return (self.min + self.max) * 0.5;
+    }
+    /// Is the box finite on every axis
+    pub fn is_finite(&self) -> bool {
+        return self.min.x.is_finite()
+            && self.min.y.is_finite()
+            && self.min.z.is_finite()
+            && self.max.x.is_finite()
+            && self.max.y.is_finite()
+            && self.max.z.is_finite();
+    }
+    /// Get the surface area of the box, zero if empty
+    pub fn surface_area(&self) -> f64 {
+        let extent = self.max - self.min;
+        if extent.x < 0.0 || extent.y < 0.0 || extent.z < 0.0 {
+            return 0.0;
+        }
+        return 2.0 * (extent.x * extent.y + extent.y * extent.z + extent.z * extent.x);
+    }
+    /// Compute the optional distance at which the ray enters the box with the slab method,
+    /// `inverse_direction` being `1 / direction` on every axis
+    pub fn intersect(
+        &self,
+        origin: Vec3,
+        inverse_direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<f64> {
+        let t1 = (self.min - origin).mul_elem(inverse_direction);
+        let t2 = (self.max - origin).mul_elem(inverse_direction);
+        let near = t1.min(t2);
+        let far = t1.max(t2);
+        let enter = near.x.max(near.y).max(near.z).max(distance_min);
+        let exit = far.x.min(far.y).min(far.z).min(distance_max);
+        if enter <= exit {
+            return Some(enter);
+        }
+        return None;
+    }
+}
@@ -0,0 +1,318 @@
+use crate::math::vec::Vec3;
+
+use super::aabb::Aabb;
+use super::shape::Hit;
+
+// number of buckets the centroids are binned into when looking for a split
+const BIN_COUNT: usize = 12;
+// maximum number of items in a leaf
+const LEAF_SIZE: usize = 4;
+// cost of traversing a node relatively to intersecting an item
+const TRAVERSAL_COST: f64 = 1.0;
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    // items are `items[first..first + count]`
+    Leaf { first: usize, count: usize },
+    // children indices in the nodes
+    Inner { left: usize, right: usize },
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    aabb: Aabb,
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone)]
+/// Bounding volume hierarchy over items known by their index and bounding box,
+/// built with the surface area heuristic
+pub struct Bvh {
+    nodes: Vec<Node>,
+    // items indices ordered as referenced by the leaves
+    items: Vec<usize>,
+}
+
+impl Bvh {
+    /// Build the hierarchy from the bounding box of every items
+    pub fn build(boxes: &[Aabb]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            items: (0..boxes.len()).collect(),
+        };
+        if boxes.is_empty() {
+            return bvh;
+        }
+        let centroids: Vec<Vec3> = boxes.iter().map(|aabb| aabb.center()).collect();
+        bvh.build_node(boxes, &centroids, 0, boxes.len());
+        return bvh;
+    }
+
+    /// Number of items in the hierarchy
+    pub fn len(&self) -> usize {
+        return self.items.len();
+    }
+
+    /// Is the hierarchy without items
+    pub fn is_empty(&self) -> bool {
+        return self.items.is_empty();
+    }
+
+    /// Update the bounding boxes after the items moved, keeping the structure.
+    /// Faster than a rebuild but the hierarchy quality degrades with large motions
+    pub fn refit(&mut self, boxes: &[Aabb]) {
+        // children are always stored after their parent
+        for index in (0..self.nodes.len()).rev() {
+            let aabb = match self.nodes[index].kind {
+                NodeKind::Leaf { first, count } => self.items[first..first + count]
+                    .iter()
+                    .fold(Aabb::empty(), |aabb, item| aabb.union(boxes[*item])),
+                NodeKind::Inner { left, right } => {
+                    self.nodes[left].aabb.union(self.nodes[right].aabb)
+                }
+            };
+            self.nodes[index].aabb = aabb;
+        }
+    }
+
+    /// Recursively build the node of the items `items[first..first + count]`,
+    /// returns its index
+    fn build_node(
+        &mut self,
+        boxes: &[Aabb],
+        centroids: &[Vec3],
+        first: usize,
+        count: usize,
+    ) -> usize {
+        let items = &mut self.items[first..first + count];
+        let aabb = items
+            .iter()
+            .fold(Aabb::empty(), |aabb, item| aabb.union(boxes[*item]));
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            aabb,
+            kind: NodeKind::Leaf { first, count },
+        });
+        if count <= LEAF_SIZE {
+            return index;
+        }
+        // find the best split with the surface area heuristic
+        let centroid_aabb = items
+            .iter()
+            .fold(Aabb::empty(), |aabb, item| aabb.grow(centroids[*item]));
+        let opt_split = find_split(boxes, centroids, items, &centroid_aabb);
+        let (axis, position, cost) = match opt_split {
+            Some(split) => split,
+            // every centroids are at the same position
+            None => return index,
+        };
+        let leaf_cost = count as f64;
+        if cost >= leaf_cost && count <= 4 * LEAF_SIZE {
+            return index;
+        }
+        // partition the items on each side of the split
+        let mut middle = 0;
+        for i in 0..items.len() {
+            if centroids[items[i]].axis(axis) < position {
+                items.swap(i, middle);
+                middle += 1;
+            }
+        }
+        // fallback to the median when the split is degenerated
+        if middle == 0 || middle == count {
+            items.sort_by(|a, b| {
+                centroids[*a]
+                    .axis(axis)
+                    .total_cmp(&centroids[*b].axis(axis))
+            });
+            middle = count / 2;
+        }
+        let left = self.build_node(boxes, centroids, first, middle);
+        let right = self.build_node(boxes, centroids, first + middle, count - middle);
+        self.nodes[index].kind = NodeKind::Inner { left, right };
+        return index;
+    }
+
+    /// Find the closest hit along the ray,
+    /// `intersect` being called with an item and the current maximum distance
+    pub fn closest_hit<F>(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+        mut intersect: F,
+    ) -> Option<(Hit, usize)>
+    where
+        F: FnMut(usize, f64) -> Option<Hit>,
+    {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let inverse_direction = inverse(direction);
+        let mut opt_result: Option<(Hit, usize)> = None;
+        let mut closest_distance = distance_max;
+        let mut stack: Vec<usize> = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            // skip nodes further than the closest hit
+            if node
+                .aabb
+                .intersect(origin, inverse_direction, distance_min, closest_distance)
+                .is_none()
+            {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { first, count } => {
+                    for item in self.items[first..first + count].iter() {
+                        if let Some(hit) = intersect(*item, closest_distance) {
+                            closest_distance = hit.distance;
+                            opt_result = Some((hit, *item));
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    // visit the nearest child first to shrink the closest distance sooner
+                    let distance_left = self.nodes[left].aabb.intersect(
+                        origin,
+                        inverse_direction,
+                        distance_min,
+                        closest_distance,
+                    );
+                    let distance_right = self.nodes[right].aabb.intersect(
+                        origin,
+                        inverse_direction,
+                        distance_min,
+                        closest_distance,
+                    );
+                    match (distance_left, distance_right) {
+                        (Some(l), Some(r)) => {
+                            if l < r {
+                                stack.push(right);
+                                stack.push(left);
+                            } else {
+                                stack.push(left);
+                                stack.push(right);
+                            }
+                        }
+                        (Some(_), None) => stack.push(left),
+                        (None, Some(_)) => stack.push(right),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+        return opt_result;
+    }
+
+    /// Find whether any item is hit along the ray, stopping at the first one,
+    /// `is_hit` being called with an item
+    pub fn any_hit<F>(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+        mut is_hit: F,
+    ) -> bool
+    where
+        F: FnMut(usize) -> bool,
+    {
+        if self.nodes.is_empty() {
+            return false;
+        }
+        let inverse_direction = inverse(direction);
+        let mut stack: Vec<usize> = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node
+                .aabb
+                .intersect(origin, inverse_direction, distance_min, distance_max)
+                .is_none()
+            {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { first, count } => {
+                    if self.items[first..first + count]
+                        .iter()
+                        .any(|item| is_hit(*item))
+                    {
+                        return true;
+                    }
+                }
+                NodeKind::Inner { left, right } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        return false;
+    }
+}
+
+/// Inverse every components of the direction for the slab test
+fn inverse(direction: Vec3) -> Vec3 {
+    return Vec3::new(1.0 / direction.x, 1.0 / direction.y, 1.0 / direction.z);
+}
+
+/// Find the split of the items with the lowest surface area heuristic cost
+/// by binning their centroids, returns the axis, the position and the cost
+/// relatively to intersecting a single item
+fn find_split(
+    boxes: &[Aabb],
+    centroids: &[Vec3],
+    items: &[usize],
+    centroid_aabb: &Aabb,
+) -> Option<(usize, f64, f64)> {
+    let parent_area = items
+        .iter()
+        .fold(Aabb::empty(), |aabb, item| aabb.union(boxes[*item]))
+        .surface_area();
+    let mut opt_best: Option<(usize, f64, f64)> = None;
+    for axis in 0..3 {
+        let min = centroid_aabb.min.axis(axis);
+        let extent = centroid_aabb.max.axis(axis) - min;
+        if extent <= 0.0 {
+            continue;
+        }
+        // bin the items by centroid
+        let mut bins = [(Aabb::empty(), 0usize); BIN_COUNT];
+        for item in items.iter() {
+            let ratio = (centroids[*item].axis(axis) - min) / extent;
+            let bin = ((ratio * BIN_COUNT as f64) as usize).min(BIN_COUNT - 1);
+            bins[bin].0 = bins[bin].0.union(boxes[*item]);
+            bins[bin].1 += 1;
+        }
+        // evaluate the split after every bins but the last
+        for split in 1..BIN_COUNT {
+            let (left_aabb, left_count) = bins[..split]
+                .iter()
+                .fold((Aabb::empty(), 0), |(aabb, count), bin| {
+                    (aabb.union(bin.0), count + bin.1)
+                });
+            let (right_aabb, right_count) = bins[split..]
+                .iter()
+                .fold((Aabb::empty(), 0), |(aabb, count), bin| {
+                    (aabb.union(bin.0), count + bin.1)
+                });
+            if left_count == 0 || right_count == 0 {
+                continue;
+            }
+            let cost = if parent_area > 0.0 {
+                TRAVERSAL_COST
+                    + (left_aabb.surface_area() * left_count as f64
+                        + right_aabb.surface_area() * right_count as f64)
+                        / parent_area
+            } else {
+                TRAVERSAL_COST + items.len() as f64 / 2.0
+            };
+            if opt_best.is_none() || cost < opt_best.unwrap().2 {
+                let position = min + extent * split as f64 / BIN_COUNT as f64;
+                opt_best = Some((axis, position, cost));
+            }
+        }
+    }
+    return opt_best;
+}
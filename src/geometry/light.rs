@@ -1,6 +1,12 @@
-use crate::{material::Material, math::vec::reflection, math::vec::Vec3};
+use std::f64::consts::PI;
 
-use super::sphere::{find_intersection, Sphere};
+use crate::{
+    material::Material,
+    math::random::Random,
+    math::vec::{orthonormal_basis, reflection, Vec3},
+    render::Color,
+    scene::Scene,
+};
 
 /// Compute the specular light
 fn computer_specular(
@@ -14,7 +20,7 @@ fn computer_specular(
         return 0.0;
     }
     // maximum reflection from the light into the surface
-    let reflected = reflection(&direction, &info.normal);
+    let reflected = reflection(direction, &info.normal);
     // calculate the coefficient of intensity sent back according to the viewer position
     let coeff = reflected.dot(info.direction) / (reflected.norm() * info.direction.norm());
     // if coeff is negative no light is sent back to the viewer from is point of view
@@ -25,6 +31,51 @@ fn computer_specular(
     return intensity * coeff.powf(material.specular);
 }
 
+/// Compute the diffuse light
+fn compute_diffuse(intensity: f64, direction: &Vec3, info: &LightComputeInfo) -> f64 {
+    // coefficient of light caught according to the angle with the light,
+    // a light behind the surface does not light it
+    let coeff = direction.dot(info.normal) / (info.normal.norm() * direction.norm());
+    return intensity * coeff.max(0.0);
+}
+
+#[derive(Debug, Copy, Clone)]
+/// How the light of a positioned light decreases with the distance
+pub enum Attenuation {
+    /// Same intensity at any distance
+    None,
+    /// Intensity divided by the distance
+    Linear,
+    /// Intensity divided by the square of the distance
+    InverseSquare,
+    /// Intensity divided by `constant + linear * distance + quadratic * distance²`
+    Coefficients {
+        constant: f64,
+        linear: f64,
+        quadratic: f64,
+    },
+}
+
+impl Attenuation {
+    /// Get the coefficient applied to the intensity at the given distance
+    pub fn factor(&self, distance: f64) -> f64 {
+        let denominator = match *self {
+            Attenuation::None => 1.0,
+            Attenuation::Linear => distance,
+            Attenuation::InverseSquare => distance * distance,
+            Attenuation::Coefficients {
+                constant,
+                linear,
+                quadratic,
+            } => constant + linear * distance + quadratic * distance * distance,
+        };
+        if denominator <= 0.0 {
+            return 1.0;
+        }
+        return 1.0 / denominator;
+    }
+}
+
 pub struct LightComputeInfo {
     pub direction: Vec3,
     pub normal: Vec3,
@@ -35,35 +86,45 @@ pub struct LightComputeInfo {
 }
 
 pub trait Light {
-    /// Compute the light for every lights
-    fn compute(&self, info: &LightComputeInfo, material: &Material, spheres: &Vec<Sphere>) -> f64;
+    /// Compute the light for every lights, per color channel
+    fn compute(&self, info: &LightComputeInfo, material: &Material, scene: &Scene) -> Color;
 }
 
 pub struct LightAmbient {
     pub intensity: f64,
+    // color of the light, scaled by the intensity
+    pub color: Color,
 }
 
 impl LightAmbient {
     pub fn new(intensity: f64) -> Self {
-        LightAmbient { intensity }
+        LightAmbient {
+            intensity,
+            color: Color::white(),
+        }
+    }
+
+    /// Set the color of the light
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        return self;
     }
 }
 
 impl Light for LightAmbient {
-    fn compute(
-        &self,
-        _info: &LightComputeInfo,
-        _material: &Material,
-        _spheres: &Vec<Sphere>,
-    ) -> f64 {
+    fn compute(&self, _info: &LightComputeInfo, _material: &Material, _scene: &Scene) -> Color {
         // ambient light is ambient from wherever
-        return self.intensity;
+        return self.color * self.intensity;
     }
 }
 
 pub struct LightPoint {
     pub intensity: f64,
     pub position: Vec3,
+    // color of the light, scaled by the intensity
+    pub color: Color,
+    // decrease of the intensity with the distance
+    pub attenuation: Attenuation,
 }
 
 impl LightPoint {
@@ -71,13 +132,25 @@ impl LightPoint {
         LightPoint {
             intensity,
             position,
+            color: Color::white(),
+            attenuation: Attenuation::None,
         }
     }
 
+    /// Set the color of the light
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        return self;
+    }
+
+    /// Set the attenuation of the light with the distance
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        return self;
+    }
+
     fn compute_diffuse(&self, info: &LightComputeInfo, direction: &Vec3) -> f64 {
-        // coefficient of light caught according to the angle with the light
-        let coeff = direction.dot(info.normal) / (info.normal.norm() * direction.norm());
-        return self.intensity * coeff;
+        return compute_diffuse(self.intensity, direction, info);
     }
 
     fn compute_specular(
@@ -91,22 +164,21 @@ impl LightPoint {
 }
 
 impl Light for LightPoint {
-    fn compute(&self, info: &LightComputeInfo, material: &Material, spheres: &Vec<Sphere>) -> f64 {
+    fn compute(&self, info: &LightComputeInfo, material: &Material, scene: &Scene) -> Color {
         // light direction
         let direction = self.position - info.position;
+        let distance = direction.norm();
         // if shadow is enabled
         if info.is_shadow {
-            // try to find intersection between the light and the position
-            let opt_shadow_intersection =
-                find_intersection(info.position, direction, spheres, 0.001, 1000.0);
-            // if there is an intersection then there is a shadow
-            if opt_shadow_intersection.is_some() {
-                return 0.0;
+            // if anything is between the position and the light, not beyond, then there is a shadow
+            if scene.is_shadowed(info.position, info.normal, direction / distance, distance) {
+                return Color::black();
             }
         }
+        let attenuation = self.attenuation.factor(distance);
         // if both diffuse and specular disabled
         if !info.is_diffuse && !info.is_specular {
-            return 1.0;
+            return self.color * attenuation;
         }
         // otherwise compute each
         let mut res = 0.0;
@@ -116,13 +188,15 @@ impl Light for LightPoint {
         if info.is_specular {
             res += self.compute_specular(info, &direction, material);
         }
-        return res;
+        return self.color * (res * attenuation);
     }
 }
 
 pub struct LightDirectional {
     pub intensity: f64,
     pub direction: Vec3,
+    // color of the light, scaled by the intensity
+    pub color: Color,
 }
 
 impl LightDirectional {
@@ -130,13 +204,18 @@ impl LightDirectional {
         LightDirectional {
             intensity,
             direction,
+            color: Color::white(),
         }
     }
 
+    /// Set the color of the light
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        return self;
+    }
+
     fn compute_diffuse(&self, info: &LightComputeInfo) -> f64 {
-        // coefficient of light caught according to the angle with the light
-        let coeff = self.direction.dot(info.normal) / (info.normal.norm() * self.direction.norm());
-        return self.intensity * coeff;
+        return compute_diffuse(self.intensity, &self.direction, info);
     }
 
     fn compute_specular(&self, info: &LightComputeInfo, material: &Material) -> f64 {
@@ -145,18 +224,16 @@ impl LightDirectional {
 }
 
 impl Light for LightDirectional {
-    fn compute(&self, info: &LightComputeInfo, material: &Material, spheres: &Vec<Sphere>) -> f64 {
+    fn compute(&self, info: &LightComputeInfo, material: &Material, scene: &Scene) -> Color {
         if info.is_shadow {
-            // try to find object between the hit and the light
-            let opt_shadow_intersection =
-                find_intersection(info.position, self.direction, spheres, 0.001, 1000.0);
-            // if so return dark
-            if opt_shadow_intersection.is_some() {
-                return 0.0;
+            // if any object is between the hit and the light, infinitely far, return dark
+            let direction = self.direction.normalize();
+            if scene.is_shadowed(info.position, info.normal, direction, f64::INFINITY) {
+                return Color::black();
             }
         }
         if !info.is_diffuse && !info.is_specular {
-            return 1.0;
+            return self.color;
         }
         let mut res = 0.0;
         if info.is_diffuse {
@@ -165,6 +242,211 @@ impl Light for LightDirectional {
         if info.is_specular {
             res += self.compute_specular(info, material);
         }
-        return res;
+        return self.color * res;
+    }
+}
+
+pub struct LightSpot {
+    pub intensity: f64,
+    pub position: Vec3,
+    // direction the spot is pointing to
+    pub direction: Vec3,
+    // half angle in radians of the cone lit at full intensity
+    pub inner_angle: f64,
+    // half angle in radians of the cone outside of which nothing is lit
+    pub outer_angle: f64,
+    // color of the light, scaled by the intensity
+    pub color: Color,
+    // decrease of the intensity with the distance
+    pub attenuation: Attenuation,
+}
+
+impl LightSpot {
+    pub fn new(
+        intensity: f64,
+        position: Vec3,
+        direction: Vec3,
+        inner_angle: f64,
+        outer_angle: f64,
+    ) -> Self {
+        LightSpot {
+            intensity,
+            position,
+            direction,
+            inner_angle,
+            outer_angle,
+            color: Color::white(),
+            attenuation: Attenuation::None,
+        }
+    }
+
+    /// Set the color of the light
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        return self;
+    }
+
+    /// Set the attenuation of the light with the distance
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        return self;
+    }
+
+    /// Compute the coefficient of light reaching a position at `to_position` from the spot,
+    /// 1 inside the inner cone, 0 outside the outer cone and smooth in between
+    pub fn falloff(&self, to_position: &Vec3) -> f64 {
+        let cos = self.direction.dot(*to_position) / (self.direction.norm() * to_position.norm());
+        let cos_inner = self.inner_angle.cos();
+        let cos_outer = self.outer_angle.cos();
+        // hard edge when the cones are the same
+        if cos_inner <= cos_outer {
+            return if cos >= cos_outer { 1.0 } else { 0.0 };
+        }
+        let t = ((cos - cos_outer) / (cos_inner - cos_outer)).clamp(0.0, 1.0);
+        // smoothstep
+        return t * t * (3.0 - 2.0 * t);
+    }
+}
+
+impl Light for LightSpot {
+    fn compute(&self, info: &LightComputeInfo, material: &Material, scene: &Scene) -> Color {
+        // light direction
+        let direction = self.position - info.position;
+        let falloff = self.falloff(&-direction);
+        // outside of the cone
+        if falloff <= 0.0 {
+            return Color::black();
+        }
+        let distance = direction.norm();
+        if info.is_shadow {
+            // occluders beyond the light do not cast shadows
+            if scene.is_shadowed(info.position, info.normal, direction / distance, distance) {
+                return Color::black();
+            }
+        }
+        // both the cone and the distance reduce the light
+        let falloff = falloff * self.attenuation.factor(distance);
+        if !info.is_diffuse && !info.is_specular {
+            return self.color * falloff;
+        }
+        let mut res = 0.0;
+        if info.is_diffuse {
+            res += compute_diffuse(self.intensity, &direction, info);
+        }
+        if info.is_specular {
+            res += computer_specular(self.intensity, &direction, info, material);
+        }
+        return self.color * (res * falloff);
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Shape of the surface emitting an area light, centered on the light position
+pub enum AreaShape {
+    /// Parallelogram spanned by two edges
+    Rectangle { edge_u: Vec3, edge_v: Vec3 },
+    /// Disk facing the normal
+    Disk { normal: Vec3, radius: f64 },
+    /// Sphere, sampled on its silhouette seen from the lit position
+    Sphere { radius: f64 },
+}
+
+pub struct LightArea {
+    pub intensity: f64,
+    pub position: Vec3,
+    pub shape: AreaShape,
+    // number of shadow rays per lit position, rounded to a square grid
+    pub samples: usize,
+    // color of the light, scaled by the intensity
+    pub color: Color,
+    // decrease of the intensity with the distance
+    pub attenuation: Attenuation,
+}
+
+impl LightArea {
+    pub fn new(intensity: f64, position: Vec3, shape: AreaShape, samples: usize) -> Self {
+        LightArea {
+            intensity,
+            position,
+            shape,
+            samples,
+            color: Color::white(),
+            attenuation: Attenuation::None,
+        }
+    }
+
+    /// Set the color of the light
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        return self;
+    }
+
+    /// Set the attenuation of the light with the distance
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        return self;
+    }
+
+    /// Get the point of the light surface matching `(s, t)` in [0, 1)²,
+    /// seen from the lit `position`
+    fn sample(&self, s: f64, t: f64, position: &Vec3) -> Vec3 {
+        return match self.shape {
+            AreaShape::Rectangle { edge_u, edge_v } => {
+                self.position + edge_u * (s - 0.5) + edge_v * (t - 0.5)
+            }
+            AreaShape::Disk { normal, radius } => {
+                self.position + sample_disk(&normal.normalize(), radius, s, t)
+            }
+            AreaShape::Sphere { radius } => {
+                let normal = (*position - self.position).normalize();
+                self.position + sample_disk(&normal, radius, s, t)
+            }
+        };
+    }
+}
+
+/// Get a point of the disk centered on the origin with uniform density
+fn sample_disk(normal: &Vec3, radius: f64, s: f64, t: f64) -> Vec3 {
+    let (tangent, bitangent) = orthonormal_basis(normal);
+    let r = radius * s.sqrt();
+    let theta = 2.0 * PI * t;
+    return tangent * (r * theta.cos()) + bitangent * (r * theta.sin());
+}
+
+impl Light for LightArea {
+    /// Average the light of stratified points of the surface,
+    /// partially occluded surfaces giving penumbrae
+    fn compute(&self, info: &LightComputeInfo, material: &Material, scene: &Scene) -> Color {
+        let side = ((self.samples as f64).sqrt().round() as usize).max(1);
+        let sample_count = (side * side) as f64;
+        let intensity = self.intensity / sample_count;
+        // same jitter for the same position to avoid flickering between frames
+        let mut random = Random::from_position(&info.position);
+        let mut res = 0.0;
+        for i in 0..side {
+            for j in 0..side {
+                let s = (i as f64 + random.next_f64()) / side as f64;
+                let t = (j as f64 + random.next_f64()) / side as f64;
+                let direction = self.sample(s, t, &info.position) - info.position;
+                let distance = direction.norm();
+                if info.is_shadow
+                    && scene.is_shadowed(info.position, info.normal, direction / distance, distance)
+                {
+                    continue;
+                }
+                let attenuation = self.attenuation.factor(distance);
+                if !info.is_diffuse && !info.is_specular {
+                    res += attenuation / sample_count;
+                    continue;
+                }
+                if info.is_diffuse {
+                    res += compute_diffuse(intensity, &direction, info) * attenuation;
+                }
+                if info.is_specular {
+                    res += computer_specular(intensity, &direction, info, material) * attenuation;
+                }
+            }
+        }
+        return self.color * res;
     }
 }
@@ -0,0 +1,220 @@
+use crate::material::Material;
+use crate::math::vec::{tangent_frame, Vec3};
+
+use super::aabb::Aabb;
+use super::bvh::Bvh;
+use super::shape::{Hit, Shape};
+use super::triangle::moller_trumbore;
+
+/// An indexed triangle mesh sharing its vertices between triangles
+pub struct Mesh {
+    // position of every vertices
+    pub positions: Vec<Vec3>,
+    // optional normal of every vertices, used for smooth shading
+    pub normals: Option<Vec<Vec3>>,
+    // optional texture coordinates of every vertices
+    pub uvs: Option<Vec<(f64, f64)>>,
+    // triangles as indices of their vertices, counter clockwise
+    pub indices: Vec<[usize; 3]>,
+    pub material: Material,
+    // hierarchy over the triangles
+    bvh: Bvh,
+}
+
+impl Mesh {
+    /// Creates a new mesh, normals and uvs must be indexed as the positions
+    pub fn new(
+        positions: Vec<Vec3>,
+        normals: Option<Vec<Vec3>>,
+        uvs: Option<Vec<(f64, f64)>>,
+        indices: Vec<[usize; 3]>,
+        material: Material,
+    ) -> Result<Self, String> {
+        if let Some(index) = indices.iter().flatten().find(|i| **i >= positions.len()) {
+            return Err(format!(
+                "vertex index {} out of range of {} positions",
+                index,
+                positions.len()
+            ));
+        }
+        if normals.as_ref().is_some_and(|n| n.len() != positions.len()) {
+            return Err(String::from("normals do not match the positions"));
+        }
+        if uvs.as_ref().is_some_and(|uv| uv.len() != positions.len()) {
+            return Err(String::from("uvs do not match the positions"));
+        }
+        let mut mesh = Mesh {
+            positions,
+            normals,
+            uvs,
+            indices,
+            material,
+            bvh: Bvh::build(&[]),
+        };
+        mesh.rebuild();
+        return Ok(mesh);
+    }
+
+    /// Build the hierarchy over the triangles,
+    /// to use after editing the positions or the indices
+    pub fn rebuild(&mut self) {
+        let boxes: Vec<Aabb> = (0..self.indices.len())
+            .map(|index| self.triangle_bounding_box(index))
+            .collect();
+        self.bvh = Bvh::build(&boxes);
+    }
+
+    /// Get the bounding box of a triangle
+    fn triangle_bounding_box(&self, index: usize) -> Aabb {
+        let (a, b, c) = self.triangle(index);
+        return Aabb::empty().grow(a).grow(b).grow(c);
+    }
+
+    /// Get the vertices of a triangle
+    pub fn triangle(&self, index: usize) -> (Vec3, Vec3, Vec3) {
+        let [a, b, c] = self.indices[index];
+        return (self.positions[a], self.positions[b], self.positions[c]);
+    }
+
+    /// Get the geometric normal of a triangle
+    pub fn face_normal(&self, index: usize) -> Vec3 {
+        let (a, b, c) = self.triangle(index);
+        return (b - a).cross(c - a).normalize();
+    }
+
+    /// Compute smooth vertex normals by averaging the normals
+    /// of the triangles sharing each vertex weighted by their area
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vec3::new(0.0, 0.0, 0.0); self.positions.len()];
+        for (index, triangle) in self.indices.iter().enumerate() {
+            let (a, b, c) = self.triangle(index);
+            // the cross product norm is twice the area of the triangle
+            let weighted = (b - a).cross(c - a);
+            for vertex in triangle.iter() {
+                normals[*vertex] = normals[*vertex] + weighted;
+            }
+        }
+        self.normals = Some(normals.into_iter().map(|n| n.normalize()).collect());
+    }
+}
+
+impl Shape for Mesh {
+    fn intersect(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<Hit> {
+        return self
+            .bvh
+            .closest_hit(
+                origin,
+                direction,
+                distance_min,
+                distance_max,
+                |index, closest_distance| {
+                    let (a, b, c) = self.triangle(index);
+                    let (t, u, v) = moller_trumbore(a, b, c, origin, direction)?;
+                    if t > distance_min && t < closest_distance {
+                        let mut hit = Hit::new(origin, direction, t);
+                        hit.primitive = index;
+                        hit.barycentric = (u, v);
+                        return Some(hit);
+                    }
+                    return None;
+                },
+            )
+            .map(|(hit, _)| hit);
+    }
+
+    /// Stop at the first triangle hit
+    fn occludes(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> bool {
+        return self
+            .bvh
+            .any_hit(origin, direction, distance_min, distance_max, |index| {
+                let (a, b, c) = self.triangle(index);
+                return match moller_trumbore(a, b, c, origin, direction) {
+                    Some((t, _, _)) => t > distance_min && t < distance_max,
+                    None => false,
+                };
+            });
+    }
+
+    /// Get the smooth normal interpolated from the vertex normals if any,
+    /// otherwise the normal of the triangle
+    fn normal(&self, hit: &Hit) -> Vec3 {
+        if let Some(normals) = &self.normals {
+            let [a, b, c] = self.indices[hit.primitive];
+            let (u, v) = hit.barycentric;
+            return (normals[a] * (1.0 - u - v) + normals[b] * u + normals[c] * v).normalize();
+        }
+        return self.face_normal(hit.primitive);
+    }
+
+    /// Get the interpolated texture coordinates at the hit
+    fn uv(&self, hit: &Hit) -> Option<(f64, f64)> {
+        let uvs = self.uvs.as_ref()?;
+        let [a, b, c] = self.indices[hit.primitive];
+        let (u, v) = hit.barycentric;
+        let w = 1.0 - u - v;
+        return Some((
+            uvs[a].0 * w + uvs[b].0 * u + uvs[c].0 * v,
+            uvs[a].1 * w + uvs[b].1 * u + uvs[c].1 * v,
+        ));
+    }
+
+    /// Get the tangent frame following the texture coordinates of the triangle hit,
+    /// or the edges of the triangle without texture coordinates
+    fn tangent_frame(&self, hit: &Hit) -> (Vec3, Vec3) {
+        let normal = self.normal(hit);
+        let (a, b, c) = self.triangle(hit.primitive);
+        let (edge_1, edge_2) = (b - a, c - a);
+        let uvs = match self.uvs.as_ref() {
+            Some(uvs) => uvs,
+            None => return tangent_frame(&normal, edge_1, edge_2),
+        };
+        let [ia, ib, ic] = self.indices[hit.primitive];
+        let (du_1, dv_1) = (uvs[ib].0 - uvs[ia].0, uvs[ib].1 - uvs[ia].1);
+        let (du_2, dv_2) = (uvs[ic].0 - uvs[ia].0, uvs[ic].1 - uvs[ia].1);
+        let determinant = du_1 * dv_2 - du_2 * dv_1;
+        if determinant.abs() < 1e-12 {
+            return tangent_frame(&normal, edge_1, edge_2);
+        }
+        // solve the derivatives of the position along u and v from the edges
+        let dpdu = (edge_1 * dv_2 - edge_2 * dv_1) / determinant;
+        let dpdv = (edge_2 * du_1 - edge_1 * du_2) / determinant;
+        return tangent_frame(&normal, dpdu, dpdv);
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        return &mut self.material;
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        return self
+            .positions
+            .iter()
+            .fold(Aabb::empty(), |aabb, position| aabb.grow(*position));
+    }
+
+    fn translate(&mut self, offset: Vec3) {
+        for position in self.positions.iter_mut() {
+            *position = *position + offset;
+        }
+        let boxes: Vec<Aabb> = (0..self.indices.len())
+            .map(|index| self.triangle_bounding_box(index))
+            .collect();
+        self.bvh.refit(&boxes);
+    }
+}
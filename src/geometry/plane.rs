@@ -1,11 +1,73 @@
-use crate::math::vec::Vec3;
+use crate::material::Material;
+use crate::math::vec::{orthonormal_basis, Vec3};
+
+use super::aabb::Aabb;
+use super::shape::{Hit, Shape};
+
+/// An infinite plane of every points `p` such as `normal.dot(p) + d = 0`
 pub struct Plane {
     pub normal: Vec3,
     pub d: f64,
+    pub material: Material,
 }
 
 impl Plane {
-    pub fn new(normal: Vec3, d: f64) -> Self {
-        Plane { normal, d }
+    /// Creates a new plane, the normal is normalized
+    pub fn new(normal: Vec3, d: f64, material: Material) -> Self {
+        let norm = normal.norm();
+        Plane {
+            normal: normal / norm,
+            d: d / norm,
+            material,
+        }
+    }
+}
+
+impl Shape for Plane {
+    fn intersect(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<Hit> {
+        let denominator = self.normal.dot(direction);
+        // the ray is parallel to the plane
+        if denominator == 0.0 {
+            return None;
+        }
+        let t = -(self.normal.dot(origin) + self.d) / denominator;
+        if t > distance_min && t < distance_max {
+            return Some(Hit::new(origin, direction, t));
+        }
+        return None;
+    }
+
+    fn normal(&self, _hit: &Hit) -> Vec3 {
+        return self.normal;
+    }
+
+    /// Get the coordinates of the hit along two axes of the plane as texture coordinates
+    fn uv(&self, hit: &Hit) -> Option<(f64, f64)> {
+        let (tangent, bitangent) = orthonormal_basis(&self.normal);
+        return Some((hit.position.dot(tangent), hit.position.dot(bitangent)));
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        return &mut self.material;
+    }
+
+    /// A plane is unbounded
+    fn bounding_box(&self) -> Aabb {
+        let infinity = Vec3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        return Aabb::new(-infinity, infinity);
+    }
+
+    fn translate(&mut self, offset: Vec3) {
+        self.d -= self.normal.dot(offset);
     }
 }
@@ -0,0 +1,77 @@
+use crate::material::Material;
+use crate::math::vec::{orthonormal_basis, Vec3};
+
+use super::aabb::Aabb;
+
+#[derive(Debug, Copy, Clone)]
+/// An intersection between a ray and a shape
+pub struct Hit {
+    /// Distance along the ray i.e. the intersection is `origin + direction * distance`
+    pub distance: f64,
+    /// Position of the intersection in the scene
+    pub position: Vec3,
+    /// Index of the shape hit in the scene, set by the scene
+    pub shape: usize,
+    /// Index of the primitive hit inside the shape e.g. the triangle of a mesh
+    pub primitive: usize,
+    /// Barycentric coordinates `(u, v)` of the hit on the primitive,
+    /// the weight of the first vertex being `1 - u - v`
+    pub barycentric: (f64, f64),
+}
+
+impl Hit {
+    /// Creates a new hit from the ray and the distance along it
+    pub fn new(origin: Vec3, direction: Vec3, distance: f64) -> Self {
+        Hit {
+            distance,
+            position: origin + direction * distance,
+            shape: 0,
+            primitive: 0,
+            barycentric: (0.0, 0.0),
+        }
+    }
+}
+
+pub trait Shape {
+    /// Compute an optional intersection between the ray and the shape,
+    /// keeping only the closest one within `distance_min` and `distance_max`
+    fn intersect(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<Hit>;
+    /// Is there any intersection between the ray and the shape
+    /// within `distance_min` and `distance_max`, without looking for the closest one
+    fn occludes(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> bool {
+        return self
+            .intersect(origin, direction, distance_min, distance_max)
+            .is_some();
+    }
+    /// Get the unit normal vector of the surface at the hit
+    fn normal(&self, hit: &Hit) -> Vec3;
+    /// Get the texture coordinates of the surface at the hit, if the shape has any
+    fn uv(&self, _hit: &Hit) -> Option<(f64, f64)> {
+        return None;
+    }
+    /// Get the unit tangent and bitangent of the surface at the hit, orthogonal to the normal
+    /// and following the texture coordinates `u` and `v` when the shape has some
+    fn tangent_frame(&self, hit: &Hit) -> (Vec3, Vec3) {
+        return orthonormal_basis(&self.normal(hit));
+    }
+    /// Get the material of the shape
+    fn material(&self) -> &Material;
+    /// Get the material of the shape to change it
+    fn material_mut(&mut self) -> &mut Material;
+    /// Get the bounding box enclosing the shape
+    fn bounding_box(&self) -> Aabb;
+    /// Move the shape by the given offset
+    fn translate(&mut self, offset: Vec3);
+}
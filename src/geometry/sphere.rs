@@ -1,5 +1,10 @@
+use std::f64::consts::PI;
+
 use crate::material::Material;
-use crate::math::vec::Vec3;
+use crate::math::vec::{tangent_frame, Vec3};
+
+use super::aabb::Aabb;
+use super::shape::{Hit, Shape};
 
 pub struct Sphere {
     pub center: Vec3,
@@ -16,18 +21,22 @@ impl Sphere {
             radius,
         }
     }
+}
+
+impl Shape for Sphere {
     /// Compute an optional intersection
     /// between the ray of:
     /// * origin
     /// * direction
+    ///
     /// and the sphere
-    pub fn intersect(
+    fn intersect(
         &self,
         origin: Vec3,
         direction: Vec3,
         distance_min: f64,
         distance_max: f64,
-    ) -> Option<Vec3> {
+    ) -> Option<Hit> {
         let co: Vec3 = origin - self.center;
         // quadratic solution
         let a: f64 = direction.dot(direction);
@@ -38,7 +47,7 @@ impl Sphere {
         if discriminant == 0.0 {
             let t0 = -b / (2.0 * a);
             if t0 > distance_min && t0 < distance_max {
-                return Some(origin + direction * t0);
+                return Some(Hit::new(origin, direction, t0));
             }
         }
         // if two solutions
@@ -50,50 +59,54 @@ impl Sphere {
                 && t1 < distance_max
                 && (t1 < t2 || t2 < distance_min || t2 > distance_max)
             {
-                return Some(origin + direction * t1);
+                return Some(Hit::new(origin, direction, t1));
             }
             if t2 > distance_min
                 && t2 < distance_max
                 && (t2 < t1 || t1 < distance_min || t1 > distance_max)
             {
-                return Some(origin + direction * t2);
+                return Some(Hit::new(origin, direction, t2));
             }
         }
         return None;
     }
 
     /// Get the normal vector of the intersection
-    pub fn normal(&self, intersection: Vec3) -> Vec3 {
-        return intersection - self.center;
+    fn normal(&self, hit: &Hit) -> Vec3 {
+        return (hit.position - self.center) / self.radius;
     }
-}
 
-/// Find an optional intersection in the scenes between:
-/// * the ray
-/// * all the spheres
-pub fn find_intersection(
-    origin: Vec3,
-    direction: Vec3,
-    spheres: &Vec<Sphere>,
-    distance_min: f64,
-    distance_max: f64,
-) -> Option<(Vec3, &Sphere)> {
-    let mut opt_result: Option<(Vec3, &Sphere)> = None;
-    let mut opt_closest_distance: Option<f64> = None;
-    // for every spheres
-    for sphere in spheres.iter() {
-        // try to find a optional intersection with current sphere
-        let opt_current_intersection =
-            sphere.intersect(origin, direction, distance_min, distance_max);
-        // if interesection
-        if let Some(intersection) = opt_current_intersection {
-            let distance = (intersection - origin).norm();
-            // keep if it is closer
-            if opt_closest_distance.is_none() || distance < opt_closest_distance.unwrap() {
-                opt_closest_distance = Some(distance);
-                opt_result = Some((intersection, sphere));
-            }
-        }
+    /// Get the spherical texture coordinates of the hit,
+    /// `u` going around the vertical axis and `v` from the bottom to the top pole
+    fn uv(&self, hit: &Hit) -> Option<(f64, f64)> {
+        let normal = self.normal(hit);
+        let u = 0.5 + normal.z.atan2(normal.x) / (2.0 * PI);
+        let v = 0.5 + normal.y.clamp(-1.0, 1.0).asin() / PI;
+        return Some((u, v));
+    }
+
+    /// Get the tangent going around the vertical axis and the bitangent going to the top pole
+    fn tangent_frame(&self, hit: &Hit) -> (Vec3, Vec3) {
+        let normal = self.normal(hit);
+        // derivatives of the position along the longitude and the latitude
+        let dpdu = Vec3::new(-normal.z, 0.0, normal.x);
+        return tangent_frame(&normal, dpdu, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        return &mut self.material;
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let extent = Vec3::new(self.radius, self.radius, self.radius);
+        return Aabb::new(self.center - extent, self.center + extent);
+    }
+
+    fn translate(&mut self, offset: Vec3) {
+        self.center = self.center + offset;
     }
-    return opt_result;
 }
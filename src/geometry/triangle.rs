@@ -0,0 +1,101 @@
+use crate::material::Material;
+use crate::math::vec::{tangent_frame, Vec3};
+
+use super::aabb::Aabb;
+use super::shape::{Hit, Shape};
+
+/// Möller–Trumbore intersection between the ray and the triangle `(a, b, c)`,
+/// returns the distance along the ray and the barycentric coordinates `(u, v)`
+pub fn moller_trumbore(
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+    origin: Vec3,
+    direction: Vec3,
+) -> Option<(f64, f64, f64)> {
+    let edge_1 = b - a;
+    let edge_2 = c - a;
+    let p = direction.cross(edge_2);
+    let determinant = edge_1.dot(p);
+    // the ray is parallel to the triangle
+    if determinant.abs() < f64::EPSILON {
+        return None;
+    }
+    let inverse_determinant = 1.0 / determinant;
+    let s = origin - a;
+    let u = s.dot(p) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge_1);
+    let v = direction.dot(q) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge_2.dot(q) * inverse_determinant;
+    return Some((t, u, v));
+}
+
+pub struct Triangle {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub c: Vec3,
+    pub material: Material,
+}
+
+impl Triangle {
+    /// Creates a new triangle, the normal follows the counter clockwise order of `(a, b, c)`
+    pub fn new(a: Vec3, b: Vec3, c: Vec3, material: Material) -> Self {
+        Triangle { a, b, c, material }
+    }
+}
+
+impl Shape for Triangle {
+    fn intersect(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<Hit> {
+        let (t, u, v) = moller_trumbore(self.a, self.b, self.c, origin, direction)?;
+        if t > distance_min && t < distance_max {
+            let mut hit = Hit::new(origin, direction, t);
+            hit.barycentric = (u, v);
+            return Some(hit);
+        }
+        return None;
+    }
+
+    fn normal(&self, _hit: &Hit) -> Vec3 {
+        return (self.b - self.a).cross(self.c - self.a).normalize();
+    }
+
+    /// Get the barycentric coordinates of the hit as texture coordinates
+    fn uv(&self, hit: &Hit) -> Option<(f64, f64)> {
+        return Some(hit.barycentric);
+    }
+
+    /// Get the tangent frame following the barycentric coordinates
+    fn tangent_frame(&self, hit: &Hit) -> (Vec3, Vec3) {
+        return tangent_frame(&self.normal(hit), self.b - self.a, self.c - self.a);
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        return &mut self.material;
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        return Aabb::empty().grow(self.a).grow(self.b).grow(self.c);
+    }
+
+    fn translate(&mut self, offset: Vec3) {
+        self.a = self.a + offset;
+        self.b = self.b + offset;
+        self.c = self.c + offset;
+    }
+}
@@ -0,0 +1,107 @@
+pub mod inflate;
+pub mod mtl;
+pub mod obj;
+pub mod png;
+pub mod ppm;
+
+use std::fmt;
+
+use crate::texture::image::Image;
+use png::{decode_png, is_png};
+use ppm::{decode_ppm, is_ppm};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Kind of file being parsed
+pub enum Format {
+    Obj,
+    Mtl,
+    Png,
+    Ppm,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An error while parsing a file, with the line where it happened
+pub struct ParseError {
+    pub format: Format,
+    // line number starting from 1, 0 when the format has no lines
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(format: Format, line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            format,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self.format {
+            Format::Obj => "obj",
+            Format::Mtl => "mtl",
+            Format::Png => "png",
+            Format::Ppm => "ppm",
+        };
+        if self.line == 0 {
+            return write!(f, "{}: {}", format, self.message);
+        }
+        return write!(f, "{} line {}: {}", format, self.line, self.message);
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Decode a PNG or PPM image, the format being found from the data
+pub fn load_image(bytes: &[u8]) -> Result<Image, ParseError> {
+    if is_ppm(bytes) {
+        return decode_ppm(bytes);
+    }
+    if is_png(bytes) {
+        return decode_png(bytes);
+    }
+    return Err(ParseError::new(
+        Format::Png,
+        0,
+        "unknown image format, expected PNG or PPM",
+    ));
+}
+
+/// Parse every floats of a statement, expecting between `min` and `max` of them
+fn parse_floats(
+    format: Format,
+    line: usize,
+    keyword: &str,
+    arguments: &[&str],
+    min: usize,
+    max: usize,
+) -> Result<Vec<f64>, ParseError> {
+    if arguments.len() < min || arguments.len() > max {
+        return Err(ParseError::new(
+            format,
+            line,
+            format!(
+                "`{}` expects between {} and {} values, got {}",
+                keyword,
+                min,
+                max,
+                arguments.len()
+            ),
+        ));
+    }
+    return arguments
+        .iter()
+        .map(|argument| {
+            argument.parse::<f64>().map_err(|_| {
+                ParseError::new(
+                    format,
+                    line,
+                    format!("`{}` has an invalid number `{}`", keyword, argument),
+                )
+            })
+        })
+        .collect();
+}
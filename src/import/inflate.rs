@@ -0,0 +1,316 @@
+// maximum number of bits of a Huffman code
+const MAX_BITS: usize = 15;
+// base length and extra bits of the length symbols 257 to 285
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+// base distance and extra bits of the distance symbols 0 to 29
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// order of the code length code lengths in a dynamic block header
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Reads the bits of a DEFLATE stream, least significant bit first
+struct BitReader<'a> {
+    data: &'a [u8],
+    // index of the next byte to load
+    position: usize,
+    buffer: u32,
+    // number of bits in the buffer
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            position: 0,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32, String> {
+        while self.count < count {
+            let byte = *self
+                .data
+                .get(self.position)
+                .ok_or("unexpected end of the compressed data")?;
+            self.position += 1;
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1u64 << count) - 1) as u32;
+        self.buffer = (u64::from(self.buffer) >> count) as u32;
+        self.count -= count;
+        return Ok(value);
+    }
+
+    /// Drop the bits left in the current byte
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code decoded bit by bit
+struct Huffman {
+    // number of codes of every lengths
+    counts: [u16; MAX_BITS + 1],
+    // symbols ordered by code
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Build the code from the code length of every symbols, 0 being unused
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for length in lengths.iter() {
+            counts[*length as usize] += 1;
+        }
+        counts[0] = 0;
+        // reject over subscribed codes
+        let mut left: i32 = 1;
+        for count in counts.iter().skip(1) {
+            left = (left << 1) - i32::from(*count);
+            if left < 0 {
+                return Err(String::from("invalid Huffman code lengths"));
+            }
+        }
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0u16; offsets[MAX_BITS + 1] as usize];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                symbols[offsets[*length as usize] as usize] = symbol as u16;
+                offsets[*length as usize] += 1;
+            }
+        }
+        return Ok(Huffman { counts, symbols });
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<usize, String> {
+        // first code and index of the current length
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for length in 1..=MAX_BITS {
+            code |= reader.bits(1)? as i32;
+            let count = i32::from(self.counts[length]);
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize] as usize);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        return Err(String::from("invalid Huffman code"));
+    }
+}
+
+/// Decompress a zlib stream, e.g. the image data of a PNG,
+/// failing if it decompresses to more than `max_size` bytes
+pub fn inflate_zlib(data: &[u8], max_size: usize) -> Result<Vec<u8>, String> {
+    if data.len() < 6 {
+        return Err(String::from("zlib stream is too short"));
+    }
+    let (method, flags) = (data[0], data[1]);
+    if method & 0x0f != 8 || (u16::from(method) << 8 | u16::from(flags)) % 31 != 0 {
+        return Err(String::from("invalid zlib header"));
+    }
+    if flags & 0x20 != 0 {
+        return Err(String::from("zlib preset dictionaries are not supported"));
+    }
+    let (output, read) = inflate(&data[2..], max_size)?;
+    let checksum = data
+        .get(2 + read..2 + read + 4)
+        .ok_or("missing zlib checksum")?;
+    if u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]) != adler32(&output)
+    {
+        return Err(String::from("zlib checksum mismatch"));
+    }
+    return Ok(output);
+}
+
+/// Decompress a raw DEFLATE stream of at most `max_size` decompressed bytes,
+/// returns the data and the number of bytes read
+pub fn inflate(data: &[u8], max_size: usize) -> Result<(Vec<u8>, usize), String> {
+    let mut reader = BitReader::new(data);
+    let mut output: Vec<u8> = Vec::new();
+    loop {
+        let is_final = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => inflate_stored(&mut reader, &mut output, max_size)?,
+            1 => {
+                let (literals, distances) = fixed_codes()?;
+                inflate_block(&mut reader, &mut output, max_size, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut output, max_size, &literals, &distances)?;
+            }
+            _ => return Err(String::from("invalid DEFLATE block type")),
+        }
+        if is_final {
+            break;
+        }
+    }
+    return Ok((output, reader.position));
+}
+
+/// Copy an uncompressed block
+fn inflate_stored(
+    reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    max_size: usize,
+) -> Result<(), String> {
+    reader.align();
+    let header = reader
+        .data
+        .get(reader.position..reader.position + 4)
+        .ok_or("unexpected end of the compressed data")?;
+    let length = u16::from_le_bytes([header[0], header[1]]);
+    let complement = u16::from_le_bytes([header[2], header[3]]);
+    if length != !complement {
+        return Err(String::from("invalid stored block length"));
+    }
+    let start = reader.position + 4;
+    let block = reader
+        .data
+        .get(start..start + length as usize)
+        .ok_or("unexpected end of the compressed data")?;
+    check_size(output, block.len(), max_size)?;
+    output.extend_from_slice(block);
+    reader.position = start + length as usize;
+    return Ok(());
+}
+
+/// Codes of the blocks compressed with the fixed Huffman codes
+fn fixed_codes() -> Result<(Huffman, Huffman), String> {
+    let mut lengths = [0u8; 288];
+    for (symbol, length) in lengths.iter_mut().enumerate() {
+        *length = match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+    return Ok((Huffman::new(&lengths)?, Huffman::new(&[5u8; 30])?));
+}
+
+/// Read the codes of a block compressed with dynamic Huffman codes
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for symbol in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[*symbol] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+    // literal and distance code lengths share the repetitions
+    let mut lengths: Vec<u8> = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code_length_code.decode(reader)?;
+        let (length, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths
+                    .last()
+                    .ok_or("repeated code length without previous one")?;
+                (previous, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+        if lengths.len() + repeat > literal_count + distance_count {
+            return Err(String::from("too many code lengths"));
+        }
+        lengths.extend(std::iter::repeat_n(length, repeat));
+    }
+    if lengths[256] == 0 {
+        return Err(String::from("missing end of block code"));
+    }
+    return Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..])?,
+    ));
+}
+
+/// Decode the literals and back references of a compressed block
+fn inflate_block(
+    reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    max_size: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = literals.decode(reader)?;
+        match symbol {
+            0..=255 => {
+                check_size(output, 1, max_size)?;
+                output.push(symbol as u8);
+            }
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                if index >= LENGTH_BASE.len() {
+                    return Err(String::from("invalid length symbol"));
+                }
+                let length = LENGTH_BASE[index] + reader.bits(LENGTH_EXTRA[index])? as usize;
+                let index = distances.decode(reader)?;
+                if index >= DISTANCE_BASE.len() {
+                    return Err(String::from("invalid distance symbol"));
+                }
+                let distance = DISTANCE_BASE[index] + reader.bits(DISTANCE_EXTRA[index])? as usize;
+                if distance > output.len() {
+                    return Err(String::from("distance too far back"));
+                }
+                check_size(output, length, max_size)?;
+                // the copy may overlap what it writes
+                let start = output.len() - distance;
+                for i in 0..length {
+                    output.push(output[start + i]);
+                }
+            }
+        }
+    }
+}
+
+/// Fail if adding `count` bytes to the output goes beyond `max_size`
+fn check_size(output: &[u8], count: usize, max_size: usize) -> Result<(), String> {
+    if count > max_size - output.len() {
+        return Err(String::from("decompressed data larger than expected"));
+    }
+    return Ok(());
+}
+
+/// Adler-32 checksum of the zlib streams
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for byte in chunk.iter() {
+            a += u32::from(*byte);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    return (b << 16) | a;
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use crate::material::Material;
+use crate::render::RGBA;
+
+use super::{parse_floats, Format, ParseError};
+
+/// Material used when the file does not define one
+pub fn default_material() -> Material {
+    return Material::new(RGBA::new(255, 255, 255, 255), -1.0, 0.0);
+}
+
+/// Convert a MTL color channel from [0, 1] to [0, 255]
+fn to_channel(value: f64) -> u8 {
+    return (value * 255.0).round().clamp(0.0, 255.0) as u8;
+}
+
+/// A material being parsed
+struct PendingMaterial {
+    name: String,
+    material: Material,
+    // average of the `Ks` color
+    specular_color: f64,
+    // is the illumination model using ray traced reflection
+    is_reflection: bool,
+}
+
+impl PendingMaterial {
+    fn finish(self, materials: &mut HashMap<String, Material>) {
+        let mut material = self.material;
+        if self.is_reflection {
+            material.reflective = self.specular_color;
+        }
+        materials.insert(self.name, material);
+    }
+}
+
+/// Parse a Wavefront MTL file into materials by name, mapping:
+/// * `Kd` onto the color
+/// * `Ns` onto the specular exponent
+/// * `Ks` onto the reflective coefficient when `illum` enables ray traced reflection
+/// * `Ni` onto the refractive index
+/// * `d`, or its complement `Tr`, onto the transparency
+/// * `Ke` onto the emission, its largest channel being the strength
+pub fn parse_mtl(source: &str) -> Result<HashMap<String, Material>, ParseError> {
+    let mut materials: HashMap<String, Material> = HashMap::new();
+    let mut opt_pending: Option<PendingMaterial> = None;
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        // strip comments
+        let content = raw.split('#').next().unwrap_or("");
+        let mut tokens = content.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+        if keyword == "newmtl" {
+            if arguments.is_empty() {
+                return Err(ParseError::new(
+                    Format::Mtl,
+                    line,
+                    "`newmtl` expects a name",
+                ));
+            }
+            if let Some(pending) = opt_pending.take() {
+                pending.finish(&mut materials);
+            }
+            opt_pending = Some(PendingMaterial {
+                name: arguments.join(" "),
+                material: default_material(),
+                specular_color: 0.0,
+                is_reflection: false,
+            });
+            continue;
+        }
+        let pending = match opt_pending.as_mut() {
+            Some(pending) => pending,
+            None => {
+                return Err(ParseError::new(
+                    Format::Mtl,
+                    line,
+                    format!("`{}` appears before any `newmtl`", keyword),
+                ))
+            }
+        };
+        match keyword {
+            "Kd" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 3, 3)?;
+                pending.material.color = RGBA::new(
+                    to_channel(values[0]),
+                    to_channel(values[1]),
+                    to_channel(values[2]),
+                    255,
+                );
+            }
+            "Ns" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 1, 1)?;
+                pending.material.specular = values[0];
+            }
+            "Ks" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 3, 3)?;
+                pending.specular_color = (values[0] + values[1] + values[2]) / 3.0;
+            }
+            "Ke" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 3, 3)?;
+                let strength = values.iter().cloned().fold(0.0, f64::max);
+                if strength > 0.0 {
+                    pending.material.emission = RGBA::new(
+                        to_channel(values[0] / strength),
+                        to_channel(values[1] / strength),
+                        to_channel(values[2] / strength),
+                        255,
+                    );
+                }
+                pending.material.emission_strength = strength;
+            }
+            "Ni" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 1, 1)?;
+                pending.material.refractive_index = values[0];
+            }
+            "d" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 1, 1)?;
+                pending.material.transparency = (1.0 - values[0]).clamp(0.0, 1.0);
+            }
+            "Tr" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 1, 1)?;
+                pending.material.transparency = values[0].clamp(0.0, 1.0);
+            }
+            "illum" => {
+                let values = parse_floats(Format::Mtl, line, keyword, &arguments, 1, 1)?;
+                // illumination models 3 to 7 are the ones with ray traced reflection
+                pending.is_reflection = (3.0..=7.0).contains(&values[0]);
+            }
+            // other statements are not supported by the renderer
+            _ => {}
+        }
+    }
+    if let Some(pending) = opt_pending {
+        pending.finish(&mut materials);
+    }
+    return Ok(materials);
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use crate::geometry::mesh::Mesh;
+use crate::material::Material;
+use crate::math::vec::Vec3;
+
+use super::mtl::{default_material, parse_mtl};
+use super::{parse_floats, Format, ParseError};
+
+/// A face vertex as indices of position, texture coordinates and normal
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+/// Triangles sharing the same material
+struct Group {
+    material: Material,
+    triangles: Vec<[FaceVertex; 3]>,
+}
+
+/// Resolve a 1-based, possibly negative i.e. relative, OBJ index
+fn resolve_index(line: usize, token: &str, count: usize, kind: &str) -> Result<usize, ParseError> {
+    let index: i64 = token.parse().map_err(|_| {
+        ParseError::new(
+            Format::Obj,
+            line,
+            format!("invalid {} index `{}`", kind, token),
+        )
+    })?;
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    if index == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(ParseError::new(
+            Format::Obj,
+            line,
+            format!(
+                "{} index {} is out of range, {} defined",
+                kind, index, count
+            ),
+        ));
+    }
+    return Ok(resolved as usize);
+}
+
+/// Parse a face vertex of the form `v`, `v/vt`, `v//vn` or `v/vt/vn`
+fn parse_face_vertex(
+    line: usize,
+    token: &str,
+    positions: usize,
+    uvs: usize,
+    normals: usize,
+) -> Result<FaceVertex, ParseError> {
+    let mut parts = token.split('/');
+    let position = resolve_index(line, parts.next().unwrap_or(""), positions, "vertex")?;
+    let uv = match parts.next() {
+        Some(part) if !part.is_empty() => Some(resolve_index(line, part, uvs, "texture")?),
+        _ => None,
+    };
+    let normal = match parts.next() {
+        Some(part) if !part.is_empty() => Some(resolve_index(line, part, normals, "normal")?),
+        _ => None,
+    };
+    if parts.next().is_some() {
+        return Err(ParseError::new(
+            Format::Obj,
+            line,
+            format!("invalid face vertex `{}`", token),
+        ));
+    }
+    return Ok((position, uv, normal));
+}
+
+/// Build an indexed mesh from the triangles of a group,
+/// vertices sharing the same position, uv and normal are merged
+fn build_mesh(
+    group: Group,
+    positions: &[Vec3],
+    uvs: &[(f64, f64)],
+    normals: &[Vec3],
+) -> Result<Mesh, ParseError> {
+    let mut vertices: HashMap<FaceVertex, usize> = HashMap::new();
+    let mut mesh_positions: Vec<Vec3> = Vec::new();
+    let mut mesh_uvs: Vec<(f64, f64)> = Vec::new();
+    let mut mesh_normals: Vec<Vec3> = Vec::new();
+    let mut indices: Vec<[usize; 3]> = Vec::new();
+    // uvs and normals are only kept if every vertices have one
+    let has_uvs = group
+        .triangles
+        .iter()
+        .all(|triangle| triangle.iter().all(|vertex| vertex.1.is_some()));
+    let has_normals = group
+        .triangles
+        .iter()
+        .all(|triangle| triangle.iter().all(|vertex| vertex.2.is_some()));
+    for triangle in group.triangles.iter() {
+        let mut triangle_indices = [0; 3];
+        for (corner, vertex) in triangle.iter().enumerate() {
+            let key = (
+                vertex.0,
+                if has_uvs { vertex.1 } else { None },
+                if has_normals { vertex.2 } else { None },
+            );
+            let index = *vertices.entry(key).or_insert_with(|| {
+                mesh_positions.push(positions[key.0]);
+                if let Some(uv) = key.1 {
+                    mesh_uvs.push(uvs[uv]);
+                }
+                if let Some(normal) = key.2 {
+                    mesh_normals.push(normals[normal].normalize());
+                }
+                mesh_positions.len() - 1
+            });
+            triangle_indices[corner] = index;
+        }
+        indices.push(triangle_indices);
+    }
+    return Mesh::new(
+        mesh_positions,
+        if has_normals {
+            Some(mesh_normals)
+        } else {
+            None
+        },
+        if has_uvs { Some(mesh_uvs) } else { None },
+        indices,
+        group.material,
+    )
+    .map_err(|message| ParseError::new(Format::Obj, 0, message));
+}
+
+/// Parse a Wavefront OBJ file into one mesh per `usemtl` group,
+/// materials being looked up by name in `materials`.
+/// Polygons are triangulated as fans, which expects them to be convex
+pub fn parse_obj(
+    source: &str,
+    materials: &HashMap<String, Material>,
+) -> Result<Vec<Mesh>, ParseError> {
+    let mut positions: Vec<Vec3> = Vec::new();
+    let mut uvs: Vec<(f64, f64)> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut groups: Vec<Group> = vec![Group {
+        material: default_material(),
+        triangles: Vec::new(),
+    }];
+
+    for (index, raw) in source.lines().enumerate() {
+        let line = index + 1;
+        // strip comments
+        let content = raw.split('#').next().unwrap_or("");
+        let mut tokens = content.split_whitespace();
+        let keyword = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let arguments: Vec<&str> = tokens.collect();
+        match keyword {
+            "v" => {
+                // the optional fourth value is the weight, unused
+                let values = parse_floats(Format::Obj, line, keyword, &arguments, 3, 4)?;
+                positions.push(Vec3::new(values[0], values[1], values[2]));
+            }
+            "vt" => {
+                let values = parse_floats(Format::Obj, line, keyword, &arguments, 1, 3)?;
+                uvs.push((values[0], *values.get(1).unwrap_or(&0.0)));
+            }
+            "vn" => {
+                let values = parse_floats(Format::Obj, line, keyword, &arguments, 3, 3)?;
+                normals.push(Vec3::new(values[0], values[1], values[2]));
+            }
+            "f" => {
+                if arguments.len() < 3 {
+                    return Err(ParseError::new(
+                        Format::Obj,
+                        line,
+                        format!(
+                            "a face expects at least 3 vertices, got {}",
+                            arguments.len()
+                        ),
+                    ));
+                }
+                let face = arguments
+                    .iter()
+                    .map(|token| {
+                        parse_face_vertex(line, token, positions.len(), uvs.len(), normals.len())
+                    })
+                    .collect::<Result<Vec<FaceVertex>, ParseError>>()?;
+                // fan triangulation from the first vertex
+                let group = groups.last_mut().unwrap();
+                for i in 1..face.len() - 1 {
+                    group.triangles.push([face[0], face[i], face[i + 1]]);
+                }
+            }
+            "usemtl" => {
+                let name = arguments.join(" ");
+                let material = materials.get(&name).ok_or_else(|| {
+                    ParseError::new(Format::Obj, line, format!("unknown material `{}`", name))
+                })?;
+                groups.push(Group {
+                    material: material.clone(),
+                    triangles: Vec::new(),
+                });
+            }
+            // objects, groups, smoothing groups, libraries, lines, points
+            // and free form geometry are not rendered
+            _ => {}
+        }
+    }
+    return groups
+        .into_iter()
+        .filter(|group| !group.triangles.is_empty())
+        .map(|group| build_mesh(group, &positions, &uvs, &normals))
+        .collect();
+}
+
+/// Parse a Wavefront OBJ file and its MTL library into meshes
+pub fn load_obj(obj: &str, mtl: &str) -> Result<Vec<Mesh>, ParseError> {
+    let materials = parse_mtl(mtl)?;
+    return parse_obj(obj, &materials);
+}
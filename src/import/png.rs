@@ -0,0 +1,366 @@
+use crate::render::RGBA;
+use crate::texture::image::Image;
+
+use super::inflate::inflate_zlib;
+use super::{Format, ParseError};
+
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+// start column, start row, column step and row step of the Adam7 passes
+const ADAM7: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/// Is the data a PNG file
+pub fn is_png(bytes: &[u8]) -> bool {
+    return bytes.starts_with(&SIGNATURE);
+}
+
+fn error(message: impl Into<String>) -> ParseError {
+    return ParseError::new(Format::Png, 0, message);
+}
+
+/// Content of the IHDR chunk
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+    is_interlaced: bool,
+}
+
+impl Header {
+    /// Number of samples per pixel
+    fn channels(&self) -> usize {
+        return match self.color_type {
+            0 | 3 => 1,
+            4 => 2,
+            2 => 3,
+            _ => 4,
+        };
+    }
+
+    /// Number of bytes of a row of `width` pixels, without its filter type
+    fn stride(&self, width: usize) -> usize {
+        return (width * self.channels() * self.bit_depth as usize).div_ceil(8);
+    }
+
+    /// Number of bytes between a byte and the same one of the previous pixel
+    fn filter_distance(&self) -> usize {
+        return (self.channels() * self.bit_depth as usize / 8).max(1);
+    }
+
+    /// Start column, start row, column step and row step of the passes of the image
+    fn passes(&self) -> Vec<(usize, usize, usize, usize)> {
+        if self.is_interlaced {
+            return ADAM7.to_vec();
+        }
+        return vec![(0, 0, 1, 1)];
+    }
+
+    /// Width and height of the reduced image of a pass
+    fn pass_size(&self, pass: (usize, usize, usize, usize)) -> (usize, usize) {
+        let (column, row, column_step, row_step) = pass;
+        return (
+            (self.width + column_step - column - 1) / column_step,
+            (self.height + row_step - row - 1) / row_step,
+        );
+    }
+
+    /// Number of bytes of the filtered rows of a pass, None if it overflows
+    fn pass_data_size(&self, pass: (usize, usize, usize, usize)) -> Option<usize> {
+        let (width, height) = self.pass_size(pass);
+        if width == 0 || height == 0 {
+            return Some(0);
+        }
+        return self.stride(width).checked_add(1)?.checked_mul(height);
+    }
+}
+
+/// Decode a PNG file of any color type and bit depth, interlaced or not, into an image
+pub fn decode_png(bytes: &[u8]) -> Result<Image, ParseError> {
+    if !is_png(bytes) {
+        return Err(error("missing PNG signature"));
+    }
+    let mut opt_header: Option<Header> = None;
+    let mut palette: Vec<RGBA> = Vec::new();
+    // alpha of the palette entries, or the sample values of the transparent color
+    let mut transparency: Vec<u8> = Vec::new();
+    let mut compressed: Vec<u8> = Vec::new();
+    let mut position = SIGNATURE.len();
+    loop {
+        let chunk_header = bytes
+            .get(position..position + 8)
+            .ok_or_else(|| error("missing IEND chunk"))?;
+        let length = u32::from_be_bytes([
+            chunk_header[0],
+            chunk_header[1],
+            chunk_header[2],
+            chunk_header[3],
+        ]) as usize;
+        let kind = &chunk_header[4..8];
+        // a 32 bits length may overflow the address space of wasm32
+        let data_end = (position + 8)
+            .checked_add(length)
+            .ok_or_else(|| error("truncated chunk"))?;
+        let data = bytes
+            .get(position + 8..data_end)
+            .ok_or_else(|| error("truncated chunk"))?;
+        let crc = bytes
+            .get(data_end..data_end + 4)
+            .ok_or_else(|| error("truncated chunk"))?;
+        if u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]])
+            != crc32(&bytes[position + 4..data_end])
+        {
+            return Err(error(format!(
+                "CRC mismatch of the `{}` chunk",
+                String::from_utf8_lossy(kind)
+            )));
+        }
+        position = data_end + 4;
+        match kind {
+            b"IHDR" => opt_header = Some(parse_header(data)?),
+            b"PLTE" => {
+                palette = data
+                    .chunks_exact(3)
+                    .map(|rgb| RGBA::new(rgb[0], rgb[1], rgb[2], 255))
+                    .collect()
+            }
+            b"tRNS" => transparency = data.to_vec(),
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            // ancillary chunks are not needed to get the pixels
+            _ => {}
+        }
+    }
+    let header = opt_header.ok_or_else(|| error("missing IHDR chunk"))?;
+    if header.color_type == 3 && palette.is_empty() {
+        return Err(error("missing PLTE chunk of a palette image"));
+    }
+    for (index, alpha) in transparency.iter().enumerate() {
+        if header.color_type == 3 && index < palette.len() {
+            palette[index].a = *alpha;
+        }
+    }
+    let passes = header.passes();
+    // the header bounds the decompressed data, stopping oversized or endless streams
+    let data_size = passes
+        .iter()
+        .try_fold(0usize, |size, pass| {
+            size.checked_add(header.pass_data_size(*pass)?)
+        })
+        .ok_or_else(|| error("image data too large"))?;
+    let data = inflate_zlib(&compressed, data_size).map_err(error)?;
+    if data.len() != data_size {
+        return Err(error("not enough image data"));
+    }
+    let mut image = Image::new(
+        header.width,
+        header.height,
+        vec![RGBA::new(0, 0, 0, 0); header.width * header.height],
+    )
+    .map_err(error)?;
+    let mut offset = 0;
+    for pass in passes {
+        let (column, row, column_step, row_step) = pass;
+        let (width, height) = header.pass_size(pass);
+        if width == 0 || height == 0 {
+            continue;
+        }
+        let size = (header.stride(width) + 1) * height;
+        let pass = &data[offset..offset + size];
+        offset += size;
+        let rows = unfilter(&header, pass, width, height)?;
+        for (y, samples) in rows.iter().enumerate() {
+            for x in 0..width {
+                let pixel = to_rgba(&header, samples, x, &palette, &transparency)?;
+                image.pixels[(row + y * row_step) * header.width + column + x * column_step] =
+                    pixel;
+            }
+        }
+    }
+    return Ok(image);
+}
+
+fn parse_header(data: &[u8]) -> Result<Header, ParseError> {
+    if data.len() != 13 {
+        return Err(error("invalid IHDR chunk length"));
+    }
+    let header = Header {
+        width: u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize,
+        height: u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize,
+        bit_depth: data[8],
+        color_type: data[9],
+        is_interlaced: data[12] == 1,
+    };
+    let is_valid_depth = match header.color_type {
+        0 => [1, 2, 4, 8, 16].contains(&header.bit_depth),
+        3 => [1, 2, 4, 8].contains(&header.bit_depth),
+        2 | 4 | 6 => [8, 16].contains(&header.bit_depth),
+        _ => return Err(error(format!("invalid color type {}", header.color_type))),
+    };
+    if !is_valid_depth {
+        return Err(error(format!(
+            "invalid bit depth {} of color type {}",
+            header.bit_depth, header.color_type
+        )));
+    }
+    if data[10] != 0 || data[11] != 0 || data[12] > 1 {
+        return Err(error("unknown compression, filter or interlace method"));
+    }
+    // bounding the pixels also bounds the size of the image data
+    if Image::pixel_count(header.width, header.height).is_none() {
+        return Err(error(format!(
+            "invalid image size {}x{}",
+            header.width, header.height
+        )));
+    }
+    return Ok(header);
+}
+
+/// Reverse the filter of every rows, returns the rows of samples
+fn unfilter(
+    header: &Header,
+    data: &[u8],
+    width: usize,
+    height: usize,
+) -> Result<Vec<Vec<u8>>, ParseError> {
+    let stride = header.stride(width);
+    let distance = header.filter_distance();
+    let mut rows: Vec<Vec<u8>> = Vec::with_capacity(height);
+    let mut previous = vec![0u8; stride];
+    for line in data.chunks_exact(stride + 1) {
+        let filter = line[0];
+        let mut row = line[1..].to_vec();
+        for i in 0..stride {
+            let left = if i >= distance { row[i - distance] } else { 0 };
+            let up = previous[i];
+            let up_left = if i >= distance {
+                previous[i - distance]
+            } else {
+                0
+            };
+            let prediction = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return Err(error(format!("invalid filter type {}", filter))),
+            };
+            row[i] = row[i].wrapping_add(prediction);
+        }
+        previous = row.clone();
+        rows.push(row);
+    }
+    return Ok(rows);
+}
+
+/// Paeth predictor, the neighbor closest to `left + up - up_left`
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let distance_left = (estimate - i16::from(left)).abs();
+    let distance_up = (estimate - i16::from(up)).abs();
+    let distance_up_left = (estimate - i16::from(up_left)).abs();
+    if distance_left <= distance_up && distance_left <= distance_up_left {
+        return left;
+    }
+    if distance_up <= distance_up_left {
+        return up;
+    }
+    return up_left;
+}
+
+/// Get the sample of a channel of the pixel at column `x` of the row
+fn sample(header: &Header, row: &[u8], x: usize, channel: usize) -> u16 {
+    let index = x * header.channels() + channel;
+    return match header.bit_depth {
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+        8 => u16::from(row[index]),
+        depth => {
+            // samples packed from the most significant bits
+            let depth = depth as usize;
+            let bit = index * depth;
+            let shift = 8 - depth - bit % 8;
+            u16::from((row[bit / 8] >> shift) & ((1u16 << depth) - 1) as u8)
+        }
+    };
+}
+
+/// Get the pixel at column `x` of the row
+fn to_rgba(
+    header: &Header,
+    row: &[u8],
+    x: usize,
+    palette: &[RGBA],
+    transparency: &[u8],
+) -> Result<RGBA, ParseError> {
+    let max = (1u32 << header.bit_depth) - 1;
+    // scale a sample to 8 bits
+    let to_u8 = |value: u16| (u32::from(value) * 255 / max) as u8;
+    // is the sample the transparent one of the tRNS chunk
+    let is_transparent = |channel: usize, value: u16| {
+        transparency.len() >= 2 * (channel + 1)
+            && u16::from_be_bytes([transparency[2 * channel], transparency[2 * channel + 1]])
+                == value
+    };
+    let samples: Vec<u16> = (0..header.channels())
+        .map(|channel| sample(header, row, x, channel))
+        .collect();
+    return match header.color_type {
+        0 => {
+            let gray = to_u8(samples[0]);
+            let alpha = if is_transparent(0, samples[0]) {
+                0
+            } else {
+                255
+            };
+            Ok(RGBA::new(gray, gray, gray, alpha))
+        }
+        2 => {
+            let alpha = if (0..3).all(|c| is_transparent(c, samples[c])) {
+                0
+            } else {
+                255
+            };
+            Ok(RGBA::new(
+                to_u8(samples[0]),
+                to_u8(samples[1]),
+                to_u8(samples[2]),
+                alpha,
+            ))
+        }
+        3 => palette
+            .get(samples[0] as usize)
+            .copied()
+            .ok_or_else(|| error("palette index out of range")),
+        4 => {
+            let gray = to_u8(samples[0]);
+            Ok(RGBA::new(gray, gray, gray, to_u8(samples[1])))
+        }
+        _ => Ok(RGBA::new(
+            to_u8(samples[0]),
+            to_u8(samples[1]),
+            to_u8(samples[2]),
+            to_u8(samples[3]),
+        )),
+    };
+}
+
+/// CRC-32 of the PNG chunks
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data.iter() {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    return !crc;
+}
@@ -0,0 +1,128 @@
+use crate::render::RGBA;
+use crate::texture::image::Image;
+
+use super::{Format, ParseError};
+
+/// Is the data a PPM file, ASCII `P3` or binary `P6`
+pub fn is_ppm(bytes: &[u8]) -> bool {
+    return bytes.starts_with(b"P3") || bytes.starts_with(b"P6");
+}
+
+fn error(line: usize, message: impl Into<String>) -> ParseError {
+    return ParseError::new(Format::Ppm, line, message);
+}
+
+/// Reads the whitespace separated tokens of a PPM file, skipping comments
+struct Tokens<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    // line number starting from 1
+    line: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn next(&mut self) -> Option<&'a [u8]> {
+        // skip whitespaces and comments
+        while let Some(byte) = self.bytes.get(self.position) {
+            match byte {
+                b'#' => {
+                    while self.bytes.get(self.position).is_some_and(|b| *b != b'\n') {
+                        self.position += 1;
+                    }
+                }
+                b'\n' => {
+                    self.line += 1;
+                    self.position += 1;
+                }
+                b if b.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+        let start = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|b| !b.is_ascii_whitespace() && *b != b'#')
+        {
+            self.position += 1;
+        }
+        if start == self.position {
+            return None;
+        }
+        return Some(&self.bytes[start..self.position]);
+    }
+
+    fn number(&mut self, name: &str) -> Result<usize, ParseError> {
+        let token = self
+            .next()
+            .ok_or_else(|| error(self.line, format!("missing {}", name)))?;
+        return std::str::from_utf8(token)
+            .ok()
+            .and_then(|text| text.parse::<usize>().ok())
+            .ok_or_else(|| {
+                error(
+                    self.line,
+                    format!("invalid {} `{}`", name, String::from_utf8_lossy(token)),
+                )
+            });
+    }
+}
+
+/// Decode a PPM file, ASCII `P3` or binary `P6` of up to 16 bits per sample, into an image
+pub fn decode_ppm(bytes: &[u8]) -> Result<Image, ParseError> {
+    if !is_ppm(bytes) {
+        return Err(error(1, "missing `P3` or `P6` magic number"));
+    }
+    let is_binary = bytes[1] == b'6';
+    let mut tokens = Tokens {
+        bytes,
+        position: 2,
+        line: 1,
+    };
+    let width = tokens.number("width")?;
+    let height = tokens.number("height")?;
+    let max = tokens.number("maximum value")?;
+    let pixel_count = Image::pixel_count(width, height).ok_or_else(|| {
+        error(
+            tokens.line,
+            format!("invalid image size {}x{}", width, height),
+        )
+    })?;
+    if max == 0 || max > 65535 {
+        return Err(error(tokens.line, format!("invalid maximum value {}", max)));
+    }
+    let count = pixel_count * 3;
+    let samples: Vec<usize> = if is_binary {
+        // a single whitespace separates the header from the samples
+        let start = tokens.position + 1;
+        let size = if max > 255 { 2 } else { 1 };
+        let data = bytes
+            .get(start..start + count * size)
+            .ok_or_else(|| error(tokens.line, "not enough samples"))?;
+        data.chunks_exact(size)
+            .map(|sample| {
+                sample
+                    .iter()
+                    .fold(0usize, |value, byte| (value << 8) | *byte as usize)
+            })
+            .collect()
+    } else {
+        (0..count)
+            .map(|_| tokens.number("sample"))
+            .collect::<Result<_, _>>()?
+    };
+    if let Some(sample) = samples.iter().find(|sample| **sample > max) {
+        return Err(error(
+            tokens.line,
+            format!("sample {} above the maximum value {}", sample, max),
+        ));
+    }
+    let pixels = samples
+        .chunks_exact(3)
+        .map(|rgb| {
+            let to_u8 = |value: usize| (value * 255 / max) as u8;
+            RGBA::new(to_u8(rgb[0]), to_u8(rgb[1]), to_u8(rgb[2]), 255)
+        })
+        .collect();
+    return Image::new(width, height, pixels).map_err(|message| error(tokens.line, message));
+}
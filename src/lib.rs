@@ -1,19 +1,43 @@
 extern crate console_error_panic_hook;
 
+pub mod camera;
 pub mod geometry;
+pub mod import;
 pub mod material;
 pub mod math;
 pub mod render;
+pub mod sampling;
+pub mod scene;
+pub mod texture;
+pub mod tone_mapping;
 
+use camera::Camera;
 use geometry::{
-    light::{Light, LightAmbient, LightComputeInfo, LightDirectional, LightPoint},
-    sphere::{find_intersection, Sphere},
+    light::{Attenuation, Light, LightAmbient, LightComputeInfo, LightDirectional, LightPoint},
+    plane::Plane,
+    shape::{Hit, Shape},
+    sphere::Sphere,
 };
-use material::Material;
-use math::vec::{reflection, Vec3};
-use render::RGBA;
+use import::load_image;
+use import::obj::load_obj;
+use material::{Fresnel, Material};
+use math::random::Random;
+use math::vec::{reflection, refraction, Vec3};
+use render::{Color, RGBA};
+use sampling::{sample_offsets, AntiAliasing};
+use scene::{Scene, ShadowBias};
+use std::rc::Rc;
+use texture::bump::{NoiseBump, NormalMap, TangentFrame};
+use texture::image::{Filter, Image, ImageTexture, Wrap};
+use texture::noise::Marble;
+use texture::{CheckerUv, Texture, TexturePoint};
+use tone_mapping::{DisplayTransform, ToneMapping};
 use wasm_bindgen::prelude::*;
 
+// Offset of the reflected and refracted rays origin from the surface,
+// avoiding the surface to intersect itself
+const SECONDARY_RAY_OFFSET: f64 = 0.001;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -21,13 +45,16 @@ extern "C" {
 }
 
 #[wasm_bindgen]
-struct Raytracer {
+pub struct Raytracer {
     // A canvas used to draw pixels
     canv: render::Canvas,
-    // A camera represented by a position
-    camera: Vec3,
-    spheres: Vec<Sphere>,
+    // A camera represented by a position and an orientation
+    camera: Camera,
+    // Every shapes of the scene
+    scene: Scene,
     lights: Vec<Box<dyn Light>>,
+    // Ambient, point and directional light colors in the color space of the scene
+    light_colors: [u32; 3],
     // Is diffuse light compute
     is_diffuse: bool,
     // Is specular light compute
@@ -37,6 +64,22 @@ struct Raytracer {
     // Is reflection compute
     is_reflection: bool,
     camera_speed: f64,
+    // Exposure and tone mapping applied before writing to the canvas
+    display: DisplayTransform,
+    // Pattern of the samples inside every pixels
+    anti_aliasing: AntiAliasing,
+    // Number of samples per pixel is the square of the grid size
+    anti_aliasing_grid: usize,
+    // Difference of displayed color between neighbors refined by the adaptive anti-aliasing
+    adaptive_threshold: f64,
+    // Number of pixels refined by the last adaptive anti-aliasing
+    refined_pixels: usize,
+    // number of reflected bounces
+    recursion_depth: u8,
+    // number of refracted bounces, only spent by transparent materials
+    refraction_depth: u8,
+    // textures created from JS, referenced by their index
+    textures: Vec<Rc<dyn Texture>>,
     // quick fix for moving sphere
     moving_sphere_going_right: bool,
 }
@@ -44,7 +87,9 @@ struct Raytracer {
 #[wasm_bindgen]
 impl Raytracer {
     #[wasm_bindgen(constructor)]
-    /// Create a sphere with the given parameters
+    /// Create a sphere with the given parameters,
+    /// light colors being hexadecimal `0xRRGGBB`
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         width: usize,
         height: usize,
@@ -54,81 +99,120 @@ impl Raytracer {
         is_shadow: bool,
         is_reflection: bool,
         camera_speed: f64,
+        ambient_light_color: u32,
+        point_light_color: u32,
+        directional_light_color: u32,
     ) -> Raytracer {
-        // main sphere
-        let mut spheres: Vec<Sphere> = vec![
-            Sphere::new(
-                Vec3::new(0.0, -5001.0, 0.0),
-                5000.0,
-                Material::new(RGBA::new(255, 255, 0, 255), 1000.0, 0.1),
-            ),
-            Sphere::new(
+        // ground
+        let mut shapes: Vec<Box<dyn Shape>> = vec![
+            // checker floor
+            Box::new(Plane::new(
+                Vec3::new(0.0, 1.0, 0.0),
+                1.0,
+                Material::new(RGBA::new(255, 255, 0, 255), 1000.0, 0.1).with_texture(Rc::new(
+                    CheckerUv::new(RGBA::new(255, 255, 0, 255), RGBA::new(60, 60, 60, 255), 1.0),
+                )),
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(0.0, 0.2, 5.0),
                 1.0,
                 Material::new(RGBA::new(255, 0, 200, 255), 500.0, 0.2),
-            ),
-            Sphere::new(
+            )),
+            // glass sphere resting on the ground
+            Box::new(Sphere::new(
+                Vec3::new(1.2, -0.4, 2.8),
+                0.6,
+                Material::new(RGBA::new(240, 250, 255, 255), 1000.0, 0.1)
+                    .with_refraction(0.9, 1.5)
+                    .with_fresnel(Fresnel::Dielectric),
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(-2.0, 0.0, 4.0),
                 1.0,
-                Material::new(RGBA::new(0, 255, 0, 255), 10.0, 0.4),
-            ),
-            Sphere::new(
+                // bumpy sphere
+                Material::new(RGBA::new(0, 255, 0, 255), 10.0, 0.4)
+                    .with_bump(Rc::new(NoiseBump::new(4.0, 0.05, 3, 11))),
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(2.0, 0.0, 4.0),
                 1.0,
                 Material::new(RGBA::new(0, 0, 255, 255), 500.0, 0.3),
-            ),
-            Sphere::new(
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(0.0, 4.0, 10.0),
                 1.0,
-                Material::new(RGBA::new(120, 120, 255, 255), 500.0, 0.2),
-            ),
-            Sphere::new(
+                // marble sphere
+                Material::new(RGBA::new(120, 120, 255, 255), 500.0, 0.2).with_texture(Rc::new(
+                    Marble::new(
+                        RGBA::new(235, 235, 245, 255),
+                        RGBA::new(70, 70, 120, 255),
+                        2.0,
+                        4.0,
+                        7,
+                    ),
+                )),
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(-3.0, 5.0, 10.0),
                 1.0,
-                Material::new(RGBA::new(255, 0, 255, 255), 1000.0, 0.0),
-            ),
-            Sphere::new(
+                // glowing sphere
+                Material::new(RGBA::new(255, 0, 255, 255), 1000.0, 0.0)
+                    .with_emission(RGBA::new(255, 120, 255, 255), 1.5),
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(2.0, 2.5, 15.0),
                 1.0,
                 Material::new(RGBA::new(0, 0, 255, 255), 500.0, 0.3),
-            ),
-            Sphere::new(
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(-1.0, 3.0, 20.0),
                 1.0,
                 Material::new(RGBA::new(255, 0, 200, 255), 1500.0, 0.7),
-            ),
-            Sphere::new(
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(-2.0, 1.0, 15.0),
                 1.0,
                 Material::new(RGBA::new(0, 255, 0, 255), 10.0, 0.4),
-            ),
-            Sphere::new(
+            )),
+            Box::new(Sphere::new(
                 Vec3::new(0.0, 2.0, -5.0),
                 1.0,
                 Material::new(RGBA::new(0, 120, 255, 255), 500.0, 0.3),
-            ),
+            )),
         ];
-        // reduce number of sphere
-        spheres.truncate(std::cmp::max(sphere_number, 2));
-        Raytracer {
+        // reduce number of sphere, keeping the ground
+        shapes.truncate(std::cmp::max(sphere_number, 2));
+        let mut raytracer = Raytracer {
             canv: render::Canvas::new(width, height),
-            camera: Vec3::new(0.0, 0.0, 0.75),
-            spheres,
-            lights: vec![
-                Box::new(LightAmbient::new(0.2)),
-                Box::new(LightPoint::new(0.6, Vec3::new(2.0, 1.0, 0.0))),
-                Box::new(LightDirectional::new(0.2, Vec3::new(1.0, 4.0, 4.0))),
+            camera: Camera::new(Vec3::new(0.0, 0.0, 0.75)),
+            scene: Scene::new(shapes),
+            lights: Vec::new(),
+            light_colors: [
+                ambient_light_color,
+                point_light_color,
+                directional_light_color,
             ],
             is_diffuse,
             is_reflection,
             is_shadow,
             is_specular,
             camera_speed,
+            display: DisplayTransform::default(),
+            anti_aliasing: AntiAliasing::None,
+            anti_aliasing_grid: 1,
+            adaptive_threshold: 0.1,
+            refined_pixels: 0,
+            recursion_depth: 1,
+            refraction_depth: 2,
+            textures: Vec::new(),
             moving_sphere_going_right: true,
-        }
+        };
+        raytracer.create_lights();
+        return raytracer;
     }
 
-    // Compute the input and change camera position accordingly
+    // Compute the input and move the camera relatively to its view accordingly
+    #[allow(clippy::too_many_arguments)]
     pub fn input(
         &mut self,
         forward: bool,
@@ -140,41 +224,188 @@ impl Raytracer {
         delta_time: f64,
     ) {
         {
-            let x: f64 = map_bool_to_f64(left) * -1.0 + map_bool_to_f64(right) * 1.0;
-            let y: f64 = map_bool_to_f64(down) * -1.0 + map_bool_to_f64(up) * 1.0;
-            let z: f64 = map_bool_to_f64(backward) * -1.0 + map_bool_to_f64(forward) * 1.0;
-            self.camera =
-                self.camera + (Vec3::new(x, y, z).normalize() * delta_time * self.camera_speed);
+            let x: f64 = -map_bool_to_f64(left) + map_bool_to_f64(right);
+            let y: f64 = -map_bool_to_f64(down) + map_bool_to_f64(up);
+            let z: f64 = -map_bool_to_f64(backward) + map_bool_to_f64(forward);
+            self.camera
+                .move_relative(Vec3::new(x, y, z).normalize() * delta_time * self.camera_speed);
         }
         let going_right = self._update_moving_sphere(&delta_time);
         self.moving_sphere_going_right = going_right;
     }
 
+    /// Set the orientation of the camera in radians, see `Camera`
+    pub fn set_camera_orientation(&mut self, yaw: f64, pitch: f64, roll: f64) {
+        self.camera.yaw = yaw;
+        self.camera.pitch = pitch;
+        self.camera.roll = roll;
+    }
+
+    /// Turn the camera from the mouse motion in pixels, e.g. with pointer lock,
+    /// `sensitivity` being in radians per pixel
+    pub fn mouse_look(&mut self, dx: f64, dy: f64, sensitivity: f64) {
+        // the screen Y axis goes down
+        self.camera.turn(dx * sensitivity, -dy * sensitivity);
+    }
+
+    /// Turn the camera to look at the target, keeping the vertical axis upward
+    pub fn look_at(&mut self, x: f64, y: f64, z: f64) {
+        self.camera = Camera::look_at(
+            self.camera.position,
+            Vec3::new(x, y, z),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+    }
+
+    /// Set the supersampling of every pixels with `grid_size` × `grid_size` samples,
+    /// trading speed for smoother edges
+    pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing, grid_size: usize) {
+        self.anti_aliasing = anti_aliasing;
+        self.anti_aliasing_grid = grid_size.max(1);
+    }
+
+    /// Set the difference of displayed color, in [0, 1], between neighbor pixels
+    /// above which the adaptive anti-aliasing refines them
+    pub fn set_adaptive_threshold(&mut self, threshold: f64) {
+        self.adaptive_threshold = threshold;
+    }
+
+    /// Get the number of pixels refined by the last adaptive anti-aliasing
+    pub fn refined_pixels(&self) -> usize {
+        return self.refined_pixels;
+    }
+
+    /// Set the number of reflected bounces of every rays
+    pub fn set_recursion_depth(&mut self, recursion_depth: u8) {
+        self.recursion_depth = recursion_depth;
+    }
+
+    /// Set the number of refracted bounces of every rays, in addition to the reflected ones,
+    /// transparent materials needing at least two to be seen through
+    pub fn set_refraction_depth(&mut self, refraction_depth: u8) {
+        self.refraction_depth = refraction_depth;
+    }
+
+    /// Set the operator mapping HDR colors to the canvas
+    pub fn set_tone_mapping(&mut self, operator: ToneMapping) {
+        self.display.operator = operator;
+    }
+
+    /// Set the exposure in stops applied before the tone mapping
+    pub fn set_exposure(&mut self, exposure: f64) {
+        self.display.exposure = exposure;
+    }
+
+    /// Set the white point of the extended Reinhard tone mapping
+    pub fn set_white_point(&mut self, white_point: f64) {
+        self.display.white_point = white_point;
+    }
+
+    /// Set whether colors are sRGB encoded, disable for scenes made with linear colors
+    pub fn set_srgb(&mut self, is_srgb: bool) {
+        self.scene.is_srgb = is_srgb;
+        self.create_lights();
+    }
+
+    /// Set the bias of the shadow rays, either along the ray or along the surface normal
+    pub fn set_shadow_bias(&mut self, bias: f64, is_normal_offset: bool) {
+        self.scene.shadow_bias = if is_normal_offset {
+            ShadowBias::NormalOffset(bias)
+        } else {
+            ShadowBias::Distance(bias)
+        };
+    }
+
+    /// Add the meshes of a Wavefront OBJ file and its MTL library to the scene
+    pub fn add_obj(&mut self, obj: &str, mtl: &str) -> Result<(), JsValue> {
+        let meshes = load_obj(obj, mtl).map_err(|error| JsValue::from_str(&error.to_string()))?;
+        for mesh in meshes {
+            self.add_shape(Box::new(mesh));
+        }
+        return Ok(());
+    }
+
+    /// Create a texture from raw RGBA bytes e.g. of a JS `ImageData`,
+    /// returns its index to assign it with `set_shape_texture`
+    pub fn add_texture_rgba(
+        &mut self,
+        width: usize,
+        height: usize,
+        rgba: &[u8],
+        filter: Filter,
+        wrap: Wrap,
+    ) -> Result<usize, JsValue> {
+        let image =
+            Image::from_rgba(width, height, rgba).map_err(|error| JsValue::from_str(&error))?;
+        return Ok(self.add_image_texture(image, filter, wrap));
+    }
+
+    /// Create a texture from the bytes of a PNG or PPM file,
+    /// returns its index to assign it with `set_shape_texture`
+    pub fn add_texture_file(
+        &mut self,
+        bytes: &[u8],
+        filter: Filter,
+        wrap: Wrap,
+    ) -> Result<usize, JsValue> {
+        let image = load_image(bytes).map_err(|error| JsValue::from_str(&error.to_string()))?;
+        return Ok(self.add_image_texture(image, filter, wrap));
+    }
+
+    /// Map a texture created from JS on the shape at `shape` index
+    pub fn set_shape_texture(&mut self, shape: usize, texture: usize) -> Result<(), JsValue> {
+        let texture = self
+            .textures
+            .get(texture)
+            .cloned()
+            .ok_or_else(|| JsValue::from_str(&format!("unknown texture {}", texture)))?;
+        let material = self
+            .scene
+            .material_mut(shape)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown shape {}", shape)))?;
+        material.texture = Some(texture);
+        return Ok(());
+    }
+
+    /// Map a tangent space normal map from raw RGBA bytes on the shape at `shape` index,
+    /// `strength` scaling the details
+    pub fn set_shape_normal_map(
+        &mut self,
+        shape: usize,
+        width: usize,
+        height: usize,
+        rgba: &[u8],
+        strength: f64,
+    ) -> Result<(), JsValue> {
+        let image =
+            Image::from_rgba(width, height, rgba).map_err(|error| JsValue::from_str(&error))?;
+        let material = self
+            .scene
+            .material_mut(shape)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown shape {}", shape)))?;
+        let texture = ImageTexture::new(Rc::new(image), Filter::Bilinear, Wrap::Repeat);
+        material.bump = Some(Rc::new(NormalMap::new(texture, strength)));
+        return Ok(());
+    }
+
     pub fn draw(&mut self) -> Vec<u8> {
         console_error_panic_hook::set_once();
+        if self.anti_aliasing == AntiAliasing::Adaptive {
+            self.draw_adaptive();
+            return self.canv.render();
+        }
         // for every pixels of the canvas
         for x in -self.canv.w_max..self.canv.w_max {
             for y in -self.canv.h_max + 1..self.canv.h_max {
-                // retrieve viewport position in the scene
-                let viewport = self.canv.pixel_to_viewport(x, y) + self.camera;
-                // compute direction from camera to viewport
-                let direction = (viewport - self.camera).normalize();
-                // assign color
-                self.canv.set_pixel_from_rgba(
-                    x,
-                    y,
-                    &get_pixel_color(
-                        self.camera,
-                        direction,
-                        &self.spheres,
-                        &self.lights,
-                        1,
-                        self.is_diffuse,
-                        self.is_shadow,
-                        self.is_specular,
-                        self.is_reflection,
-                    ),
-                );
+                // same jitter for the same pixel to avoid flickering between frames
+                let mut random = Random::new(self.canv.get_pixel_flat_index(x, y) as u64);
+                let offsets =
+                    sample_offsets(self.anti_aliasing, self.anti_aliasing_grid, &mut random);
+                // average the samples in linear HDR color
+                let color = self.trace_samples(x, y, &offsets);
+                // assign color, quantized only once here
+                self.canv
+                    .set_pixel_from_color(x, y, &self.to_display(color));
             }
         }
         // return raw array of pixels
@@ -185,19 +416,21 @@ impl Raytracer {
         let speed = 1.0;
         let close_limit: f64 = 0.1;
         let mut going_right = self.moving_sphere_going_right;
-        if let Some(sphere) = self.spheres.get_mut(1) {
-            let limit_left: Vec3 = Vec3::new(-1.0, sphere.center.y, sphere.center.z);
-            let limit_right: Vec3 = Vec3::new(1.0, sphere.center.y, sphere.center.z);
-            if !going_right && (sphere.center - limit_left).norm() < close_limit {
+        if let Some(shape) = self.scene.shapes().get(1) {
+            let center = shape.bounding_box().center();
+            let limit_left: Vec3 = Vec3::new(-1.0, center.y, center.z);
+            let limit_right: Vec3 = Vec3::new(1.0, center.y, center.z);
+            if !going_right && (center - limit_left).norm() < close_limit {
                 going_right = true;
             }
-            if going_right && (sphere.center - limit_right).norm() < close_limit {
+            if going_right && (center - limit_right).norm() < close_limit {
                 going_right = false;
             }
 
             let sign = if going_right { 1.0 } else { -1.0 };
 
-            sphere.center = sphere.center + Vec3::new(speed * delta_time * sign, 0.0, 0.0);
+            self.scene
+                .translate(1, Vec3::new(speed * delta_time * sign, 0.0, 0.0));
         }
 
         // Write back to self
@@ -205,59 +438,315 @@ impl Raytracer {
     }
 }
 
+impl Raytracer {
+    /// Create the lights from their colors, decoded as every colors of the scene
+    fn create_lights(&mut self) {
+        let [ambient, point, directional] = self.light_colors.map(RGBA::from_hex);
+        self.lights = vec![
+            Box::new(LightAmbient::new(0.2).with_color(self.scene.color(ambient))),
+            Box::new(
+                LightPoint::new(0.6, Vec3::new(2.0, 1.0, 0.0))
+                    .with_color(self.scene.color(point))
+                    .with_attenuation(Attenuation::Coefficients {
+                        constant: 1.0,
+                        linear: 0.05,
+                        quadratic: 0.01,
+                    }),
+            ),
+            Box::new(
+                LightDirectional::new(0.2, Vec3::new(1.0, 4.0, 4.0))
+                    .with_color(self.scene.color(directional)),
+            ),
+        ];
+    }
+
+    /// Keep an image texture, returns its index
+    fn add_image_texture(&mut self, image: Image, filter: Filter, wrap: Wrap) -> usize {
+        self.textures
+            .push(Rc::new(ImageTexture::new(Rc::new(image), filter, wrap)));
+        return self.textures.len() - 1;
+    }
+
+    /// Compute the color seen through a position of the canvas in pixels
+    /// and the index of the shape seen if any
+    fn trace(&self, x: f64, y: f64) -> (Color, Option<usize>) {
+        // retrieve viewport position relatively to the camera
+        let viewport = self.canv.subpixel_to_viewport(x, y);
+        // compute direction from camera to viewport, oriented as the camera
+        let direction = self.camera.to_world(viewport).normalize();
+        let opt_intersection =
+            self.scene
+                .find_intersection(self.camera.position, direction, 1.0, 1000.0);
+        let opt_shape = opt_intersection.map(|(hit, _)| hit.shape);
+        let color = get_intersection_color(
+            opt_intersection,
+            direction,
+            &self.scene,
+            &self.lights,
+            self.recursion_depth,
+            self.refraction_depth,
+            self.is_diffuse,
+            self.is_shadow,
+            self.is_specular,
+            self.is_reflection,
+        );
+        return (color, opt_shape);
+    }
+
+    /// Average the colors seen through the samples of a pixel
+    fn trace_samples(&self, x: isize, y: isize, offsets: &[(f64, f64)]) -> Color {
+        let mut color = Color::black();
+        for (offset_x, offset_y) in offsets.iter() {
+            color = color + self.trace(x as f64 + offset_x, y as f64 + offset_y).0;
+        }
+        return color / offsets.len() as f64;
+    }
+
+    /// Tone map then encode back to sRGB if the scene is
+    fn to_display(&self, color: Color) -> Color {
+        let display_color = self.display.apply(color);
+        if self.scene.is_srgb {
+            return display_color.to_srgb();
+        }
+        return display_color;
+    }
+
+    /// Render one sample per pixel, then refine with more samples
+    /// only the pixels differing from their neighbors
+    fn draw_adaptive(&mut self) {
+        let (w_max, h_max) = (self.canv.w_max, self.canv.h_max);
+        let size = self.canv.pixels.len();
+        // first sample of every pixels and its displayed color
+        let mut centers = vec![Color::black(); size];
+        let mut colors = vec![Color::black(); size];
+        let mut shapes: Vec<Option<usize>> = vec![None; size];
+        let mut is_refined = vec![false; size];
+        // a first sample at the center of every pixels
+        for x in -w_max..w_max {
+            for y in -h_max + 1..h_max {
+                let index = self.canv.get_pixel_flat_index(x, y);
+                let (color, opt_shape) = self.trace(x as f64, y as f64);
+                centers[index] = color;
+                colors[index] = self.to_display(color);
+                shapes[index] = opt_shape;
+            }
+        }
+        // compare every pixels with their right and bottom neighbors
+        for x in -w_max..w_max {
+            for y in -h_max + 1..h_max {
+                let index = self.canv.get_pixel_flat_index(x, y);
+                for (neighbor_x, neighbor_y) in [(x + 1, y), (x, y - 1)] {
+                    if neighbor_x >= w_max || neighbor_y <= -h_max {
+                        continue;
+                    }
+                    let neighbor = self.canv.get_pixel_flat_index(neighbor_x, neighbor_y);
+                    let (a, b) = (colors[index], colors[neighbor]);
+                    let difference = (a.r - b.r)
+                        .abs()
+                        .max((a.g - b.g).abs())
+                        .max((a.b - b.b).abs());
+                    if difference > self.adaptive_threshold || shapes[index] != shapes[neighbor] {
+                        is_refined[index] = true;
+                        is_refined[neighbor] = true;
+                    }
+                }
+            }
+        }
+        // refine the edges with at least 2×2 jittered samples, averaged with the first one
+        let grid_size = self.anti_aliasing_grid.max(2);
+        let mut refined_pixels = 0;
+        for x in -w_max..w_max {
+            for y in -h_max + 1..h_max {
+                let index = self.canv.get_pixel_flat_index(x, y);
+                if is_refined[index] {
+                    let mut random = Random::new(index as u64);
+                    let offsets = sample_offsets(AntiAliasing::Jittered, grid_size, &mut random);
+                    let count = offsets.len() as f64;
+                    let color = (centers[index] + self.trace_samples(x, y, &offsets) * count)
+                        / (count + 1.0);
+                    colors[index] = self.to_display(color);
+                    refined_pixels += 1;
+                }
+                self.canv.set_pixel_from_color(x, y, &colors[index]);
+            }
+        }
+        self.refined_pixels = refined_pixels;
+    }
+
+    /// Add a shape to the scene
+    pub fn add_shape(&mut self, shape: Box<dyn Shape>) {
+        self.scene.add(shape);
+    }
+}
+
 /// Assign a pixel color the of the viewport
+#[allow(clippy::too_many_arguments)]
 fn get_pixel_color(
     origin: Vec3,
     direction: Vec3,
-    spheres: &Vec<Sphere>,
-    lights: &Vec<Box<dyn Light>>,
+    scene: &Scene,
+    lights: &[Box<dyn Light>],
     recursion_depth: u8,
+    refraction_depth: u8,
     is_diffuse: bool,
     is_shadow: bool,
     is_specular: bool,
     is_reflection: bool,
-) -> RGBA {
+) -> Color {
     // find and optional intersection
-    let opt_intersection = find_intersection(origin, direction, &spheres, 1.0, 1000.0);
+    let opt_intersection = scene.find_intersection(origin, direction, 0.0, 1000.0);
+    return get_intersection_color(
+        opt_intersection,
+        direction,
+        scene,
+        lights,
+        recursion_depth,
+        refraction_depth,
+        is_diffuse,
+        is_shadow,
+        is_specular,
+        is_reflection,
+    );
+}
+
+/// Compute the color of an optional intersection of a ray going in the direction
+#[allow(clippy::too_many_arguments)]
+fn get_intersection_color(
+    opt_intersection: Option<(Hit, &dyn Shape)>,
+    direction: Vec3,
+    scene: &Scene,
+    lights: &[Box<dyn Light>],
+    recursion_depth: u8,
+    refraction_depth: u8,
+    is_diffuse: bool,
+    is_shadow: bool,
+    is_specular: bool,
+    is_reflection: bool,
+) -> Color {
     // if intersected
-    if opt_intersection.is_some() {
+    if let Some((hit, shape)) = opt_intersection {
         // retrieve the information of the intersection
-        let (intersection, sphere) = opt_intersection.unwrap();
-        let normal = sphere.normal(intersection);
+        let material = shape.material();
+        let geometric_normal = shape.normal(&hit);
+        // a ray leaving the shape hits the inside of the surface
+        let is_inside = geometric_normal.dot(direction) > 0.0;
+        let texture_point = TexturePoint {
+            position: hit.position,
+            uv: shape.uv(&hit),
+        };
+        let mut normal = match &material.bump {
+            Some(bump) => {
+                let (tangent, bitangent) = shape.tangent_frame(&hit);
+                let frame = TangentFrame {
+                    normal: geometric_normal,
+                    tangent,
+                    bitangent,
+                };
+                let bumped = bump.perturb(&texture_point, &frame);
+                // keep the surface facing the same side at grazing angles
+                if (bumped.dot(direction) > 0.0) == is_inside {
+                    bumped
+                } else {
+                    geometric_normal
+                }
+            }
+            None => geometric_normal,
+        };
+        if is_inside {
+            normal = -normal;
+        }
         let light_compute_info = LightComputeInfo {
-            position: intersection,
+            position: hit.position,
             direction,
             normal,
             is_diffuse,
             is_shadow,
             is_specular,
         };
+        // color of the surface at the intersection
+        let color = scene.color(material.color_at(&texture_point));
         // compute the light of the intersection
-        let recursion_color = compute_light(&lights, &light_compute_info, &sphere, &spheres);
-        // if not reflection just return the color
-        if sphere.material.reflective <= 0.0 || recursion_depth <= 0 || !is_reflection {
+        let mut recursion_color =
+            compute_light(lights, &light_compute_info, material, color, scene);
+        // the ray goes from the outside medium to the material or the other way around
+        let eta = if is_inside {
+            material.refractive_index
+        } else {
+            1.0 / material.refractive_index
+        };
+        let reflectance = material.reflectance(-normal.dot(direction), eta);
+        let is_reflected = reflectance > 0.0 && is_reflection && recursion_depth > 0;
+        let is_refracted = material.transparency > 0.0 && refraction_depth > 0;
+        if !is_reflected && !is_refracted {
             return recursion_color;
         }
-        // otherwise recurse on get_pixel_color
-        // by faking a camera at the position of the intersection
-        // and direction of the reflection
-        let reflected = reflection(&direction, &normal);
-        let reflected_color = get_pixel_color(
-            intersection,
-            reflected,
-            spheres,
-            lights,
-            recursion_depth - 1,
-            is_diffuse,
-            is_shadow,
-            is_specular,
-            is_reflection,
-        );
-        return recursion_color * (1.0 - sphere.material.reflective)
-            + reflected_color * sphere.material.reflective;
+        let opt_refracted = if is_refracted {
+            refraction(&direction, &normal, eta)
+        } else {
+            None
+        };
+        // the reflection is needed by reflective materials and by total internal reflection,
+        // the latter spending a refracted bounce
+        let is_total_internal_reflection = is_refracted && opt_refracted.is_none();
+        let mut reflected_color = Color::black();
+        if is_reflected || is_total_internal_reflection {
+            let (reflected_depth, reflected_refraction_depth) = if is_reflected {
+                (recursion_depth - 1, refraction_depth)
+            } else {
+                (recursion_depth, refraction_depth - 1)
+            };
+            // recurse on get_pixel_color
+            // by faking a camera at the position of the intersection
+            // and direction of the reflection
+            let reflected = reflection(&direction, &normal);
+            reflected_color = get_pixel_color(
+                hit.position + normal * SECONDARY_RAY_OFFSET,
+                reflected,
+                scene,
+                lights,
+                reflected_depth,
+                reflected_refraction_depth,
+                is_diffuse,
+                is_shadow,
+                is_specular,
+                is_reflection,
+            );
+        }
+        // part of the local color, and so of the emission, kept by the blends
+        let mut local_weight = 1.0;
+        if is_refracted {
+            let refracted_color = match opt_refracted {
+                // continue through the surface, tinted by the material
+                Some(refracted) => {
+                    get_pixel_color(
+                        hit.position - normal * SECONDARY_RAY_OFFSET,
+                        refracted,
+                        scene,
+                        lights,
+                        recursion_depth,
+                        refraction_depth - 1,
+                        is_diffuse,
+                        is_shadow,
+                        is_specular,
+                        is_reflection,
+                    ) * color
+                }
+                // total internal reflection
+                None => reflected_color,
+            };
+            recursion_color = recursion_color * (1.0 - material.transparency)
+                + refracted_color * material.transparency;
+            local_weight *= 1.0 - material.transparency;
+        }
+        if is_reflected {
+            recursion_color = recursion_color * (1.0 - reflectance) + reflected_color * reflectance;
+            local_weight *= 1.0 - reflectance;
+        }
+        // the emitted light is not reflected nor transmitted by the surface
+        return recursion_color + material.emitted(scene) * (1.0 - local_weight);
     // if no intersection returns background color
     } else {
-        return RGBA::new(85, 200, 253, 255);
+        return scene.color(RGBA::new(85, 200, 253, 255));
     }
 }
 
@@ -265,22 +754,20 @@ fn get_pixel_color(
 /// * position of the intersection in the scene
 /// * direction od the ray
 /// * normal of the surface
+/// * color of the surface
 fn compute_light(
-    lights: &Vec<Box<dyn Light>>,
+    lights: &[Box<dyn Light>],
     light_compute_info: &LightComputeInfo,
-    sphere: &Sphere,
-    spheres: &Vec<Sphere>,
-) -> RGBA {
-    let mut lighting: f64 = 0.0;
-    // retrieve lighting for every single light
+    material: &Material,
+    color: Color,
+    scene: &Scene,
+) -> Color {
+    let mut lighting = Color::black();
+    // retrieve lighting for every single light, channel by channel
     for light in lights.iter() {
-        lighting += light.compute(light_compute_info, &sphere.material, spheres);
+        lighting = lighting + light.compute(light_compute_info, material, scene);
     }
-    return if lighting > 0.0 {
-        sphere.material.color * lighting
-    } else {
-        sphere.material.color * 0.0
-    };
+    return color * lighting + material.emitted(scene);
 }
 
 /// Helper to map a boolean value to a f64
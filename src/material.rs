@@ -1,20 +1,168 @@
-use crate::render::RGBA;
+use std::rc::Rc;
 
+use crate::render::{Color, RGBA};
+use crate::scene::Scene;
+use crate::texture::bump::Bump;
+use crate::texture::{Texture, TexturePoint};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// How the reflected part of the light varies with the view angle
+pub enum Fresnel {
+    /// Constant reflective coefficient whatever the angle
+    None,
+    /// Schlick approximation, the reflective coefficient of opaque materials
+    /// being their reflectance at normal incidence
+    Schlick,
+    /// Exact Fresnel equations of a dielectric from the refractive index
+    Dielectric,
+}
+
+#[derive(Debug, Clone)]
 pub struct Material {
     // color of the material
     pub color: RGBA,
+    // texture i.e. the color varying over the surface, in place of the color
+    pub texture: Option<Rc<dyn Texture>>,
+    // bump i.e. the small details changing the shading normal, from a normal map or procedural
+    pub bump: Option<Rc<dyn Bump>>,
     // specular i.e. the amount of light sent back by the material
     pub specular: f64,
     // reflective i.e. is the material acting as "mirror"
     pub reflective: f64,
+    // transparency i.e. the amount of light going through the material
+    pub transparency: f64,
+    // refractive index i.e. how much light bends entering the material
+    pub refractive_index: f64,
+    // fresnel i.e. how the reflective coefficient varies with the view angle
+    pub fresnel: Fresnel,
+    // emission i.e. the color of the light sent by the material itself
+    pub emission: RGBA,
+    // emission strength i.e. the intensity of the emitted light, possibly above 1.0
+    pub emission_strength: f64,
 }
 
 impl Material {
     pub fn new(color: RGBA, specular: f64, reflective: f64) -> Self {
         return Material {
             color,
+            texture: None,
+            bump: None,
             specular,
             reflective,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            fresnel: Fresnel::None,
+            emission: RGBA::new(0, 0, 0, 255),
+            emission_strength: 0.0,
+        };
+    }
+
+    /// Make the color of the material vary over the surface
+    pub fn with_texture(mut self, texture: Rc<dyn Texture>) -> Self {
+        self.texture = Some(texture);
+        return self;
+    }
+
+    /// Get the color of the material at a point of the surface
+    pub fn color_at(&self, point: &TexturePoint) -> RGBA {
+        return match &self.texture {
+            Some(texture) => texture.color(point),
+            None => self.color,
         };
     }
+
+    /// Make the shading normal of the material vary over the surface
+    pub fn with_bump(mut self, bump: Rc<dyn Bump>) -> Self {
+        self.bump = Some(bump);
+        return self;
+    }
+
+    /// Make the material transparent e.g. glass with a refractive index of 1.5
+    pub fn with_refraction(mut self, transparency: f64, refractive_index: f64) -> Self {
+        self.transparency = transparency;
+        self.refractive_index = refractive_index;
+        return self;
+    }
+
+    /// Make the reflection vary with the view angle
+    pub fn with_fresnel(mut self, fresnel: Fresnel) -> Self {
+        self.fresnel = fresnel;
+        return self;
+    }
+
+    /// Make the material glow whatever the lights of the scene
+    pub fn with_emission(mut self, emission: RGBA, emission_strength: f64) -> Self {
+        self.emission = emission;
+        self.emission_strength = emission_strength;
+        return self;
+    }
+
+    /// Is the material sending light by itself, i.e. a light source
+    pub fn is_emissive(&self) -> bool {
+        return self.emission_strength > 0.0
+            && (self.emission.r > 0 || self.emission.g > 0 || self.emission.b > 0);
+    }
+
+    /// Get the linear light emitted by the material,
+    /// its emission color being decoded as the other colors of the scene
+    pub fn emitted(&self, scene: &Scene) -> Color {
+        if !self.is_emissive() {
+            return Color::black();
+        }
+        return scene.color(self.emission) * self.emission_strength;
+    }
+
+    /// Get the part of the light reflected by the surface from
+    /// * the cosine of the angle between the ray and the normal
+    /// * the ratio of the refractive indices from the incident to the transmitted medium
+    pub fn reflectance(&self, cos_incident: f64, eta: f64) -> f64 {
+        return match self.fresnel {
+            Fresnel::None => self.reflective,
+            Fresnel::Schlick => {
+                let reflectance_normal = if self.transparency > 0.0 {
+                    ((1.0 - eta) / (1.0 + eta)).powi(2)
+                } else {
+                    self.reflective
+                };
+                fresnel_schlick(cos_incident, eta, reflectance_normal)
+            }
+            Fresnel::Dielectric => fresnel_dielectric(cos_incident, eta),
+        };
+    }
+}
+
+/// Schlick approximation of the reflectance from
+/// * the cosine of the angle between the ray and the normal
+/// * the ratio of the refractive indices from the incident to the transmitted medium
+/// * the reflectance at normal incidence
+pub fn fresnel_schlick(cos_incident: f64, eta: f64, reflectance_normal: f64) -> f64 {
+    let mut cos = cos_incident.abs().min(1.0);
+    // going to a less dense medium the transmitted angle is the largest
+    if eta > 1.0 {
+        let sin_transmitted_squared = eta * eta * (1.0 - cos * cos);
+        if sin_transmitted_squared > 1.0 {
+            return 1.0;
+        }
+        cos = (1.0 - sin_transmitted_squared).sqrt();
+    }
+    return reflectance_normal + (1.0 - reflectance_normal) * (1.0 - cos).powi(5);
+}
+
+/// Exact reflectance of unpolarized light on a dielectric from
+/// * the cosine of the angle between the ray and the normal
+/// * the ratio of the refractive indices from the incident to the transmitted medium
+///
+/// returns 1.0 on total internal reflection
+pub fn fresnel_dielectric(cos_incident: f64, eta: f64) -> f64 {
+    let cos_incident = cos_incident.abs().min(1.0);
+    let sin_transmitted_squared = eta * eta * (1.0 - cos_incident * cos_incident);
+    if sin_transmitted_squared > 1.0 {
+        return 1.0;
+    }
+    let cos_transmitted = (1.0 - sin_transmitted_squared).sqrt();
+    // perpendicular and parallel polarizations
+    let perpendicular =
+        (eta * cos_incident - cos_transmitted) / (eta * cos_incident + cos_transmitted);
+    let parallel = (cos_incident - eta * cos_transmitted) / (cos_incident + eta * cos_transmitted);
+    return (perpendicular * perpendicular + parallel * parallel) / 2.0;
 }
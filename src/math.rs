@@ -1 +1,3 @@
+pub mod noise;
+pub mod random;
 pub mod vec;
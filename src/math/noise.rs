@@ -0,0 +1,119 @@
+use super::random::Random;
+use super::vec::Vec3;
+
+#[derive(Debug, Clone)]
+/// Seedable gradient noise (improved Perlin noise),
+/// the same seed always giving the same noise
+pub struct Perlin {
+    // shuffled lattice indices, repeated twice to avoid wrapping
+    permutation: Vec<u8>,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut random = Random::new(seed);
+        let mut permutation: Vec<u8> = (0..=255).collect();
+        // Fisher–Yates shuffle
+        for i in (1..permutation.len()).rev() {
+            let j = random.next_u32() as usize % (i + 1);
+            permutation.swap(i, j);
+        }
+        permutation.extend_from_within(..);
+        return Perlin { permutation };
+    }
+
+    /// Get the noise at the point, in [-1, 1] and 0 at every integer coordinates
+    pub fn noise(&self, point: Vec3) -> f64 {
+        let (x0, y0, z0) = (point.x.floor(), point.y.floor(), point.z.floor());
+        // position in the lattice cell
+        let (x, y, z) = (point.x - x0, point.y - y0, point.z - z0);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let xi = (x0 as i64).rem_euclid(256) as usize;
+        let yi = (y0 as i64).rem_euclid(256) as usize;
+        let zi = (z0 as i64).rem_euclid(256) as usize;
+        let p = &self.permutation;
+        let hash =
+            |i: usize, j: usize, k: usize| p[p[p[xi + i] as usize + yi + j] as usize + zi + k];
+        // blend the gradients of the eight corners of the cell
+        let lerp = |t: f64, a: f64, b: f64| a + t * (b - a);
+        return lerp(
+            w,
+            lerp(
+                v,
+                lerp(
+                    u,
+                    gradient(hash(0, 0, 0), x, y, z),
+                    gradient(hash(1, 0, 0), x - 1.0, y, z),
+                ),
+                lerp(
+                    u,
+                    gradient(hash(0, 1, 0), x, y - 1.0, z),
+                    gradient(hash(1, 1, 0), x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    gradient(hash(0, 0, 1), x, y, z - 1.0),
+                    gradient(hash(1, 0, 1), x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    gradient(hash(0, 1, 1), x, y - 1.0, z - 1.0),
+                    gradient(hash(1, 1, 1), x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+        .clamp(-1.0, 1.0);
+    }
+
+    /// Fractional Brownian motion, the sum of `octaves` noises of doubling frequency
+    /// and halving amplitude, in [-1, 1]
+    pub fn fbm(&self, point: Vec3, octaves: usize) -> f64 {
+        return self.octaves(point, octaves, |noise| noise);
+    }
+
+    /// Sum of the absolute values of `octaves` noises of doubling frequency
+    /// and halving amplitude, in [0, 1], with creases where the noise crosses 0
+    pub fn turbulence(&self, point: Vec3, octaves: usize) -> f64 {
+        return self.octaves(point, octaves, f64::abs);
+    }
+
+    /// Normalized sum of the octaves of the noise, each one mapped by `map`
+    fn octaves(&self, point: Vec3, octaves: usize, map: fn(f64) -> f64) -> f64 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves.max(1) {
+            sum += map(self.noise(point * frequency)) * amplitude;
+            total_amplitude += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        return sum / total_amplitude;
+    }
+}
+
+/// Smooth step `6t⁵ - 15t⁴ + 10t³` with null first and second derivatives at 0 and 1
+fn fade(t: f64) -> f64 {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+/// Dot product between the offset and one of the 12 gradients
+/// pointing to the edges of a cube, picked by the hash
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    return u + v;
+}
@@ -0,0 +1,42 @@
+use super::vec::Vec3;
+
+/// Small deterministic pseudo random numbers generator (PCG32)
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a new generator, the same seed always gives the same numbers
+    pub fn new(seed: u64) -> Self {
+        let mut random = Random { state: 0 };
+        random.next_u32();
+        random.state = random.state.wrapping_add(seed);
+        random.next_u32();
+        return random;
+    }
+
+    /// Creates a new generator seeded by a position, e.g. to get
+    /// the same samples for the same point of the scene from one frame to another
+    pub fn from_position(position: &Vec3) -> Self {
+        let seed = position.x.to_bits()
+            ^ position.y.to_bits().rotate_left(21)
+            ^ position.z.to_bits().rotate_left(42);
+        return Random::new(seed);
+    }
+
+    /// Get the next random 32 bits
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let xor_shifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        return xor_shifted.rotate_right(rotation);
+    }
+
+    /// Get the next random number in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        return f64::from(self.next_u32()) / 4294967296.0;
+    }
+}
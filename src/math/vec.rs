@@ -7,6 +7,53 @@ pub fn reflection(direction: &Vec3, normal: &Vec3) -> Vec3 {
     return *direction - *normal * 2.0 * normal.dot(*direction);
 }
 
+/// Find the refraction vector with Snell's law from
+/// * the unit direction that hit the surface
+/// * the unit normal of the surface, facing the direction
+/// * the ratio of the refractive indices from the incident to the transmitted medium
+///
+/// returns None on total internal reflection
+pub fn refraction(direction: &Vec3, normal: &Vec3, eta: f64) -> Option<Vec3> {
+    let cos_incident = -normal.dot(*direction);
+    let sin_transmitted_squared = eta * eta * (1.0 - cos_incident * cos_incident);
+    if sin_transmitted_squared > 1.0 {
+        return None;
+    }
+    let cos_transmitted = (1.0 - sin_transmitted_squared).sqrt();
+    return Some(*direction * eta + *normal * (eta * cos_incident - cos_transmitted));
+}
+
+/// Find two unit vectors orthogonal to the unit normal and to each other
+pub fn orthonormal_basis(normal: &Vec3) -> (Vec3, Vec3) {
+    // pick the axis the least aligned with the normal
+    let axis = if normal.x.abs() < 0.9 {
+        Vec3::new(1.0, 0.0, 0.0)
+    } else {
+        Vec3::new(0.0, 1.0, 0.0)
+    };
+    let tangent = normal.cross(axis).normalize();
+    let bitangent = normal.cross(tangent);
+    return (tangent, bitangent);
+}
+
+/// Find the unit tangent and bitangent orthogonal to the unit normal
+/// following the derivatives of the surface position along the texture coordinates `u` and `v`,
+/// falling back to any orthonormal basis when they are degenerated
+pub fn tangent_frame(normal: &Vec3, dpdu: Vec3, dpdv: Vec3) -> (Vec3, Vec3) {
+    // remove the part along the normal
+    let tangent = dpdu - *normal * normal.dot(dpdu);
+    if tangent.norm() < 1e-12 {
+        return orthonormal_basis(normal);
+    }
+    let tangent = tangent.normalize();
+    let bitangent = normal.cross(tangent);
+    // keep the bitangent on the side of increasing v
+    if bitangent.dot(dpdv) < 0.0 {
+        return (tangent, -bitangent);
+    }
+    return (tangent, bitangent);
+}
+
 #[derive(Debug, Copy, Clone)]
 /// Vec3 of f64
 pub struct Vec3 {
@@ -23,6 +70,34 @@ impl Vec3 {
     pub fn dot(self, _rhs: Self) -> f64 {
         return self.x * _rhs.x + self.y * _rhs.y + self.z * _rhs.z;
     }
+    /// Compute the cross product from one vector to another
+    pub fn cross(self, _rhs: Self) -> Vec3 {
+        return Vec3::new(
+            self.y * _rhs.z - self.z * _rhs.y,
+            self.z * _rhs.x - self.x * _rhs.z,
+            self.x * _rhs.y - self.y * _rhs.x,
+        );
+    }
+    /// Multiply component wise one vector by another
+    pub fn mul_elem(self, _rhs: Self) -> Vec3 {
+        return Vec3::new(self.x * _rhs.x, self.y * _rhs.y, self.z * _rhs.z);
+    }
+    /// Get a component by axis index, 0 for x, 1 for y and 2 for z
+    pub fn axis(self, axis: usize) -> f64 {
+        return match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        };
+    }
+    /// Get the component wise minimum of two vectors
+    pub fn min(self, _rhs: Self) -> Vec3 {
+        return Vec3::new(self.x.min(_rhs.x), self.y.min(_rhs.y), self.z.min(_rhs.z));
+    }
+    /// Get the component wise maximum of two vectors
+    pub fn max(self, _rhs: Self) -> Vec3 {
+        return Vec3::new(self.x.max(_rhs.x), self.y.max(_rhs.y), self.z.max(_rhs.z));
+    }
     /// Get the norm
     pub fn norm(self) -> f64 {
         return self.dot(self).sqrt();
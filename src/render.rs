@@ -8,7 +8,7 @@ extern "C" {
     fn log(s: &str);
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RGBA {
     pub r: u8,
     pub g: u8,
@@ -21,6 +21,12 @@ impl RGBA {
         RGBA { r, g, b, a }
     }
 
+    /// Creates an opaque RGBA from an hexadecimal `0xRRGGBB`
+    pub fn from_hex(hex: u32) -> Self {
+        let channel = |shift: u32| ((hex >> shift) & 0xff) as u8;
+        return RGBA::new(channel(16), channel(8), channel(0), 255);
+    }
+
     pub fn unpack(self) -> [u8; 4] {
         return [self.r, self.g, self.b, self.a];
     }
@@ -29,6 +35,7 @@ impl RGBA {
 impl ops::Mul<f64> for RGBA {
     type Output = Self;
     /// Multiply RGBA by f64
+    #[allow(clippy::manual_clamp)]
     fn mul(self, _rhs: f64) -> Self {
         let mut r_f = f64::from(self.r) * _rhs;
         let mut g_f = f64::from(self.g) * _rhs;
@@ -68,6 +75,110 @@ impl ops::Add<Self> for RGBA {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A linear high dynamic range color where 1.0 is the full channel,
+/// channels are not clamped until quantized into RGBA
+pub struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl Color {
+    pub fn new(r: f64, g: f64, b: f64) -> Self {
+        Color { r, g, b }
+    }
+
+    pub fn black() -> Self {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    pub fn white() -> Self {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    /// Decode an sRGB encoded RGBA into a linear color, the alpha is dropped
+    pub fn from_srgb(rgba: RGBA) -> Self {
+        let channel = |value: u8| srgb_to_linear(f64::from(value) / 255.0);
+        return Color::new(channel(rgba.r), channel(rgba.g), channel(rgba.b));
+    }
+
+    /// Encode a linear color in [0, 1] into sRGB
+    pub fn to_srgb(self) -> Self {
+        return Color::new(
+            linear_to_srgb(self.r),
+            linear_to_srgb(self.g),
+            linear_to_srgb(self.b),
+        );
+    }
+
+    /// Quantize the color into an opaque RGBA, clamping every channel
+    pub fn to_rgba(self) -> RGBA {
+        let channel = |value: f64| (value * 255.0).round().clamp(0.0, 255.0) as u8;
+        return RGBA::new(channel(self.r), channel(self.g), channel(self.b), 255);
+    }
+}
+
+/// Convert an sRGB encoded channel in [0, 1] to linear
+pub fn srgb_to_linear(value: f64) -> f64 {
+    if value <= 0.04045 {
+        return value / 12.92;
+    }
+    return ((value + 0.055) / 1.055).powf(2.4);
+}
+
+/// Convert a linear channel in [0, 1] to sRGB encoded
+pub fn linear_to_srgb(value: f64) -> f64 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.0031308 {
+        return value * 12.92;
+    }
+    return 1.055 * value.powf(1.0 / 2.4) - 0.055;
+}
+
+impl From<RGBA> for Color {
+    /// Convert every channel from [0, 255] to [0, 1], the alpha is dropped
+    fn from(rgba: RGBA) -> Self {
+        return Color::new(
+            f64::from(rgba.r) / 255.0,
+            f64::from(rgba.g) / 255.0,
+            f64::from(rgba.b) / 255.0,
+        );
+    }
+}
+
+impl ops::Add<Self> for Color {
+    type Output = Self;
+    /// Add two colors
+    fn add(self, _rhs: Color) -> Self {
+        return Self::new(self.r + _rhs.r, self.g + _rhs.g, self.b + _rhs.b);
+    }
+}
+
+impl ops::Div<f64> for Color {
+    type Output = Self;
+    /// Divide every channel by f64
+    fn div(self, _rhs: f64) -> Self {
+        return Self::new(self.r / _rhs, self.g / _rhs, self.b / _rhs);
+    }
+}
+
+impl ops::Mul<f64> for Color {
+    type Output = Self;
+    /// Multiply every channel by f64
+    fn mul(self, _rhs: f64) -> Self {
+        return Self::new(self.r * _rhs, self.g * _rhs, self.b * _rhs);
+    }
+}
+
+impl ops::Mul<Self> for Color {
+    type Output = Self;
+    /// Multiply two colors channel by channel
+    fn mul(self, _rhs: Color) -> Self {
+        return Self::new(self.r * _rhs.r, self.g * _rhs.g, self.b * _rhs.b);
+    }
+}
+
 /// A 2D Canvas
 #[derive(Debug, Clone)]
 pub struct Canvas {
@@ -109,6 +220,10 @@ impl Canvas {
     pub fn set_pixel_from_rgba(&mut self, x: isize, y: isize, rgba: &RGBA) {
         self.set_pixel(x, y, rgba.r, rgba.g, rgba.b, rgba.a)
     }
+    /// Set pixel color from an HDR color, quantized to RGBA
+    pub fn set_pixel_from_color(&mut self, x: isize, y: isize, color: &Color) {
+        self.set_pixel_from_rgba(x, y, &color.to_rgba())
+    }
     /// Render "flat" raw array to frontend
     pub fn render(&self) -> Vec<u8> {
         let map: Vec<[u8; 4]> = self
@@ -125,6 +240,11 @@ impl Canvas {
         // to float
         let x_f: f64 = (x as i32).into();
         let y_f: f64 = (y as i32).into();
+        return self.subpixel_to_viewport(x_f, y_f);
+    }
+    /// Convert a position of the canvas in pixels, possibly between pixels,
+    /// to a position in the scene on the viewport
+    pub fn subpixel_to_viewport(&self, x_f: f64, y_f: f64) -> Vec3 {
         let viewport_width: f64 = (self.viewport.width as u32).into();
         let viewport_height: f64 = (self.viewport.height as u32).into();
         let width: f64 = (self.width as u32).into();
@@ -0,0 +1,73 @@
+use crate::math::random::Random;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Pattern of the samples taken inside every pixels
+pub enum AntiAliasing {
+    /// A single sample at the center of the pixel
+    None,
+    /// Samples at the center of the cells of a regular N×N grid
+    Grid,
+    /// One sample at a random position inside every cell of the N×N grid
+    Jittered,
+    /// Regular N×N grid rotated so that no two samples share a row or a column
+    RotatedGrid,
+    /// A single sample, then N×N jittered samples, at least 2×2, added to it
+    /// only for the pixels differing from their neighbors
+    Adaptive,
+}
+
+/// Get the offsets of the samples from the center of the pixel, in pixels within [-0.5, 0.5),
+/// for `grid_size` × `grid_size` samples
+pub fn sample_offsets(
+    anti_aliasing: AntiAliasing,
+    grid_size: usize,
+    random: &mut Random,
+) -> Vec<(f64, f64)> {
+    let n = grid_size.max(1);
+    let cell = 1.0 / n as f64;
+    let mut offsets = Vec::with_capacity(n * n);
+    match anti_aliasing {
+        // adaptive starts from the center, refined pixels being jittered
+        AntiAliasing::None | AntiAliasing::Adaptive => offsets.push((0.0, 0.0)),
+        AntiAliasing::Grid => {
+            for i in 0..n {
+                for j in 0..n {
+                    offsets.push(((i as f64 + 0.5) * cell - 0.5, (j as f64 + 0.5) * cell - 0.5));
+                }
+            }
+        }
+        AntiAliasing::Jittered => {
+            for i in 0..n {
+                for j in 0..n {
+                    offsets.push((
+                        (i as f64 + random.next_f64()) * cell - 0.5,
+                        (j as f64 + random.next_f64()) * cell - 0.5,
+                    ));
+                }
+            }
+        }
+        AntiAliasing::RotatedGrid => {
+            // rotating by atan(1/N) spreads the N×N samples over distinct rows and columns,
+            // wrapping them back into the pixel keeps the pattern periodic
+            let angle = (1.0 / n as f64).atan();
+            let (sin, cos) = angle.sin_cos();
+            for i in 0..n {
+                for j in 0..n {
+                    let x = (i as f64 + 0.5) * cell - 0.5;
+                    let y = (j as f64 + 0.5) * cell - 0.5;
+                    let rotated_x = x * cos - y * sin;
+                    let rotated_y = x * sin + y * cos;
+                    offsets.push((wrap(rotated_x), wrap(rotated_y)));
+                }
+            }
+        }
+    }
+    return offsets;
+}
+
+/// Wrap a value into [-0.5, 0.5)
+fn wrap(value: f64) -> f64 {
+    return value - (value + 0.5).floor();
+}
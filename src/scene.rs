@@ -0,0 +1,194 @@
+use crate::geometry::bvh::Bvh;
+use crate::geometry::shape::{Hit, Shape};
+use crate::material::Material;
+use crate::math::vec::Vec3;
+use crate::render::{Color, RGBA};
+
+#[derive(Debug, Copy, Clone)]
+/// Bias avoiding surfaces to shadow themselves because of floating point errors
+pub enum ShadowBias {
+    /// Ignore occluders closer than the distance along the shadow ray
+    Distance(f64),
+    /// Move the shadow ray origin away from the surface along its normal by the distance
+    NormalOffset(f64),
+}
+
+/// Every shapes of the scene with a hierarchy accelerating the ray queries
+pub struct Scene {
+    // bias applied to every shadow rays
+    pub shadow_bias: ShadowBias,
+    // are the colors of the scene sRGB encoded, otherwise taken as linear
+    pub is_srgb: bool,
+    shapes: Vec<Box<dyn Shape>>,
+    // shapes with a finite bounding box, indexed as the items of the hierarchy
+    bounded: Vec<usize>,
+    // shapes without finite bounding box e.g. planes, tested one by one
+    unbounded: Vec<usize>,
+    bvh: Bvh,
+}
+
+impl Scene {
+    /// Creates a new scene and build its hierarchy
+    pub fn new(shapes: Vec<Box<dyn Shape>>) -> Self {
+        let mut scene = Scene {
+            shadow_bias: ShadowBias::NormalOffset(0.001),
+            is_srgb: true,
+            shapes,
+            bounded: Vec::new(),
+            unbounded: Vec::new(),
+            bvh: Bvh::build(&[]),
+        };
+        scene.rebuild();
+        return scene;
+    }
+
+    /// Convert a color of the scene, e.g. of a material, into a linear color
+    pub fn color(&self, rgba: RGBA) -> Color {
+        if self.is_srgb {
+            return Color::from_srgb(rgba);
+        }
+        return Color::from(rgba);
+    }
+
+    /// Get every shapes of the scene
+    pub fn shapes(&self) -> &[Box<dyn Shape>] {
+        return &self.shapes;
+    }
+
+    /// Add a shape to the scene, rebuilding the hierarchy
+    pub fn add(&mut self, shape: Box<dyn Shape>) {
+        self.shapes.push(shape);
+        self.rebuild();
+    }
+
+    /// Get the material of a shape to change it, the hierarchy being left as is
+    pub fn material_mut(&mut self, index: usize) -> Option<&mut Material> {
+        return self.shapes.get_mut(index).map(|shape| shape.material_mut());
+    }
+
+    /// Move a shape by the given offset, refitting the hierarchy
+    pub fn translate(&mut self, index: usize, offset: Vec3) {
+        if let Some(shape) = self.shapes.get_mut(index) {
+            shape.translate(offset);
+            self.refit();
+        }
+    }
+
+    /// Build the hierarchy from scratch, to use after large changes of the scene
+    pub fn rebuild(&mut self) {
+        let (bounded, unbounded): (Vec<usize>, Vec<usize>) = (0..self.shapes.len())
+            .partition(|index| self.shapes[*index].bounding_box().is_finite());
+        let boxes: Vec<_> = bounded
+            .iter()
+            .map(|index| self.shapes[*index].bounding_box())
+            .collect();
+        self.bvh = Bvh::build(&boxes);
+        self.bounded = bounded;
+        self.unbounded = unbounded;
+    }
+
+    /// Update the hierarchy after shapes moved
+    pub fn refit(&mut self) {
+        let boxes: Vec<_> = self
+            .bounded
+            .iter()
+            .map(|index| self.shapes[*index].bounding_box())
+            .collect();
+        self.bvh.refit(&boxes);
+    }
+
+    /// Find an optional closest intersection in the scene between:
+    /// * the ray
+    /// * all the shapes
+    pub fn find_intersection(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> Option<(Hit, &dyn Shape)> {
+        let mut opt_result = self
+            .bvh
+            .closest_hit(
+                origin,
+                direction,
+                distance_min,
+                distance_max,
+                |item, closest_distance| {
+                    self.shapes[self.bounded[item]].intersect(
+                        origin,
+                        direction,
+                        distance_min,
+                        closest_distance,
+                    )
+                },
+            )
+            .map(|(mut hit, item)| {
+                hit.shape = self.bounded[item];
+                (hit, self.shapes[hit.shape].as_ref())
+            });
+        let mut closest_distance = opt_result.map_or(distance_max, |(hit, _)| hit.distance);
+        for index in self.unbounded.iter() {
+            let shape = self.shapes[*index].as_ref();
+            if let Some(mut hit) =
+                shape.intersect(origin, direction, distance_min, closest_distance)
+            {
+                closest_distance = hit.distance;
+                hit.shape = *index;
+                opt_result = Some((hit, shape));
+            }
+        }
+        return opt_result;
+    }
+
+    /// Is there any shape between `distance_min` and `distance_max` along the ray,
+    /// stopping at the first one found e.g. for shadow rays
+    pub fn is_occluded(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        distance_min: f64,
+        distance_max: f64,
+    ) -> bool {
+        // unbounded shapes are few and large, likely to occlude
+        if self.unbounded.iter().any(|index| {
+            self.shapes[*index].occludes(origin, direction, distance_min, distance_max)
+        }) {
+            return true;
+        }
+        return self
+            .bvh
+            .any_hit(origin, direction, distance_min, distance_max, |item| {
+                self.shapes[self.bounded[item]].occludes(
+                    origin,
+                    direction,
+                    distance_min,
+                    distance_max,
+                )
+            });
+    }
+
+    /// Is a light at `distance` along the unit `direction` from the surface `position`
+    /// hidden by any shape, the shadow bias being applied along the surface `normal`
+    pub fn is_shadowed(
+        &self,
+        position: Vec3,
+        normal: Vec3,
+        direction: Vec3,
+        distance: f64,
+    ) -> bool {
+        return match self.shadow_bias {
+            ShadowBias::Distance(bias) => self.is_occluded(position, direction, bias, distance),
+            ShadowBias::NormalOffset(offset) => {
+                // offset on the side of the surface facing the light
+                let side = if normal.dot(direction) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                let origin = position + normal * (offset * side);
+                self.is_occluded(origin, direction, 0.0, distance)
+            }
+        };
+    }
+}
@@ -0,0 +1,184 @@
+pub mod bump;
+pub mod image;
+pub mod noise;
+
+use std::fmt::Debug;
+
+use crate::math::vec::Vec3;
+use crate::render::RGBA;
+
+#[derive(Debug, Copy, Clone)]
+/// Where a texture is evaluated on a surface
+pub struct TexturePoint {
+    // position of the hit in the scene
+    pub position: Vec3,
+    // texture coordinates of the hit, if the shape has any
+    pub uv: Option<(f64, f64)>,
+}
+
+/// A color varying over the surface of a shape,
+/// its colors being in the color space of the scene as `Material.color`
+pub trait Texture: Debug {
+    /// Get the color of the texture at the point
+    fn color(&self, point: &TexturePoint) -> RGBA;
+}
+
+/// Linear interpolation between two colors, `t` being in [0, 1]
+pub fn mix(a: RGBA, b: RGBA, t: f64) -> RGBA {
+    // blend every channels in floating point before rounding once
+    let channel = |a: u8, b: u8| {
+        (f64::from(a) * (1.0 - t) + f64::from(b) * t)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+    return RGBA::new(
+        channel(a.r, b.r),
+        channel(a.g, b.g),
+        channel(a.b, b.b),
+        channel(a.a, b.a),
+    );
+}
+
+/// Is the sum of the cells at the coordinates even
+fn is_even_cell(coordinates: &[f64]) -> bool {
+    let sum: i64 = coordinates.iter().map(|c| c.floor() as i64).sum();
+    return sum.rem_euclid(2) == 0;
+}
+
+#[derive(Debug, Copy, Clone)]
+/// The same color everywhere
+pub struct Solid {
+    pub color: RGBA,
+}
+
+impl Solid {
+    pub fn new(color: RGBA) -> Self {
+        return Solid { color };
+    }
+}
+
+impl Texture for Solid {
+    fn color(&self, _point: &TexturePoint) -> RGBA {
+        return self.color;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Cubes of alternating colors filling the scene
+pub struct Checker3d {
+    pub even: RGBA,
+    pub odd: RGBA,
+    // size of the side of a cube
+    pub size: f64,
+}
+
+impl Checker3d {
+    pub fn new(even: RGBA, odd: RGBA, size: f64) -> Self {
+        return Checker3d { even, odd, size };
+    }
+}
+
+impl Texture for Checker3d {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let position = point.position / self.size;
+        if is_even_cell(&[position.x, position.y, position.z]) {
+            return self.even;
+        }
+        return self.odd;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Squares of alternating colors in texture coordinates,
+/// shapes without texture coordinates taking the even color
+pub struct CheckerUv {
+    pub even: RGBA,
+    pub odd: RGBA,
+    // number of squares along each texture coordinate per unit
+    pub frequency: f64,
+}
+
+impl CheckerUv {
+    pub fn new(even: RGBA, odd: RGBA, frequency: f64) -> Self {
+        return CheckerUv {
+            even,
+            odd,
+            frequency,
+        };
+    }
+}
+
+impl Texture for CheckerUv {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let (u, v) = point.uv.unwrap_or((0.0, 0.0));
+        if is_even_cell(&[u * self.frequency, v * self.frequency]) {
+            return self.even;
+        }
+        return self.odd;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Bands of alternating colors along a direction
+pub struct Stripes {
+    pub even: RGBA,
+    pub odd: RGBA,
+    // unit direction across the bands
+    pub direction: Vec3,
+    // width of a band
+    pub width: f64,
+}
+
+impl Stripes {
+    /// Creates new stripes, the direction is normalized
+    pub fn new(even: RGBA, odd: RGBA, direction: Vec3, width: f64) -> Self {
+        return Stripes {
+            even,
+            odd,
+            direction: direction.normalize(),
+            width,
+        };
+    }
+}
+
+impl Texture for Stripes {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        if is_even_cell(&[point.position.dot(self.direction) / self.width]) {
+            return self.even;
+        }
+        return self.odd;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Blend of two colors between two positions, constant beyond them
+pub struct Gradient {
+    pub start_color: RGBA,
+    pub end_color: RGBA,
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Gradient {
+    pub fn new(start_color: RGBA, end_color: RGBA, start: Vec3, end: Vec3) -> Self {
+        return Gradient {
+            start_color,
+            end_color,
+            start,
+            end,
+        };
+    }
+}
+
+impl Texture for Gradient {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let axis = self.end - self.start;
+        let length_squared = axis.dot(axis);
+        if length_squared == 0.0 {
+            return self.start_color;
+        }
+        // projection of the position on the axis
+        let t = ((point.position - self.start).dot(axis) / length_squared).clamp(0.0, 1.0);
+        return mix(self.start_color, self.end_color, t);
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::Debug;
+
+use crate::math::noise::Perlin;
+use crate::math::vec::Vec3;
+
+use super::image::ImageTexture;
+use super::{Texture, TexturePoint};
+
+// step along the tangents of the finite differences of the height
+const HEIGHT_STEP: f64 = 1e-4;
+
+#[derive(Debug, Copy, Clone)]
+/// Unit normal of a surface with the unit tangent and bitangent following its texture coordinates
+pub struct TangentFrame {
+    pub normal: Vec3,
+    pub tangent: Vec3,
+    pub bitangent: Vec3,
+}
+
+impl TangentFrame {
+    /// Express a direction of the tangent space, `z` being along the normal, in the scene
+    pub fn to_world(&self, direction: Vec3) -> Vec3 {
+        return self.tangent * direction.x
+            + self.bitangent * direction.y
+            + self.normal * direction.z;
+    }
+}
+
+/// Small details of a surface changing its shading normal without changing its geometry
+pub trait Bump: Debug {
+    /// Get the unit shading normal at the point of the surface
+    fn perturb(&self, point: &TexturePoint, frame: &TangentFrame) -> Vec3;
+}
+
+#[derive(Debug, Clone)]
+/// Tangent space normals stored in the colors of an image,
+/// red along the tangent, green along the bitangent and blue along the normal
+pub struct NormalMap {
+    pub texture: ImageTexture,
+    // scale of the tangent part of the normals, 0.0 flattening the surface
+    pub strength: f64,
+}
+
+impl NormalMap {
+    pub fn new(texture: ImageTexture, strength: f64) -> Self {
+        return NormalMap { texture, strength };
+    }
+}
+
+impl Bump for NormalMap {
+    fn perturb(&self, point: &TexturePoint, frame: &TangentFrame) -> Vec3 {
+        let color = self.texture.color(point);
+        // map every channel from [0, 255] to [-1, 1]
+        let to_unit = |channel: u8| f64::from(channel) / 255.0 * 2.0 - 1.0;
+        let direction = Vec3::new(
+            to_unit(color.r) * self.strength,
+            to_unit(color.g) * self.strength,
+            to_unit(color.b).max(0.0),
+        );
+        if direction.norm() == 0.0 {
+            return frame.normal;
+        }
+        return frame.to_world(direction).normalize();
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Procedural bumps following the height of a fractal noise over the scene
+pub struct NoiseBump {
+    // number of bumps per unit
+    pub frequency: f64,
+    // height of the bumps
+    pub amplitude: f64,
+    pub octaves: usize,
+    noise: Perlin,
+}
+
+impl NoiseBump {
+    pub fn new(frequency: f64, amplitude: f64, octaves: usize, seed: u64) -> Self {
+        return NoiseBump {
+            frequency,
+            amplitude,
+            octaves,
+            noise: Perlin::new(seed),
+        };
+    }
+
+    /// Height of the surface at the position
+    fn height(&self, position: Vec3) -> f64 {
+        return self.amplitude * self.noise.fbm(position * self.frequency, self.octaves);
+    }
+}
+
+impl Bump for NoiseBump {
+    fn perturb(&self, point: &TexturePoint, frame: &TangentFrame) -> Vec3 {
+        // slopes of the height along the tangents
+        let height = self.height(point.position);
+        let slope_tangent =
+            (self.height(point.position + frame.tangent * HEIGHT_STEP) - height) / HEIGHT_STEP;
+        let slope_bitangent =
+            (self.height(point.position + frame.bitangent * HEIGHT_STEP) - height) / HEIGHT_STEP;
+        return (frame.normal - frame.tangent * slope_tangent - frame.bitangent * slope_bitangent)
+            .normalize();
+    }
+}
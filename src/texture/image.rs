@@ -0,0 +1,180 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::render::RGBA;
+
+use super::{Texture, TexturePoint};
+
+// largest number of pixels of an image, 4096 × 4096
+pub const MAX_PIXELS: usize = 1 << 24;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// How an image is sampled between its pixels
+pub enum Filter {
+    /// Color of the closest pixel
+    Nearest,
+    /// Blend of the four closest pixels
+    Bilinear,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// How an image is addressed outside of the [0, 1] texture coordinates
+pub enum Wrap {
+    /// Tile the image
+    Repeat,
+    /// Extend the pixels of the border
+    Clamp,
+    /// Tile the image, flipping every other tile
+    Mirror,
+}
+
+impl Wrap {
+    /// Map a pixel index into `[0, size)`
+    fn index(&self, index: i64, size: usize) -> usize {
+        let size = size as i64;
+        let index = match self {
+            Wrap::Repeat => index.rem_euclid(size),
+            Wrap::Clamp => index.clamp(0, size - 1),
+            Wrap::Mirror => {
+                let index = index.rem_euclid(2 * size);
+                if index < size {
+                    index
+                } else {
+                    2 * size - 1 - index
+                }
+            }
+        };
+        return index as usize;
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Pixels of an image, row by row from the top left corner
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<RGBA>,
+}
+
+impl Image {
+    /// Creates a new image with one pixel per column and row
+    pub fn new(width: usize, height: usize, pixels: Vec<RGBA>) -> Result<Self, String> {
+        let count = Image::pixel_count(width, height)
+            .ok_or_else(|| format!("invalid image size {}x{}", width, height))?;
+        if pixels.len() != count {
+            return Err(format!(
+                "expected {} pixels for a {}x{} image, got {}",
+                count,
+                width,
+                height,
+                pixels.len()
+            ));
+        }
+        return Ok(Image {
+            width,
+            height,
+            pixels,
+        });
+    }
+
+    /// Get the number of pixels of an image of the size,
+    /// None if it is empty or has more than `MAX_PIXELS` pixels
+    pub fn pixel_count(width: usize, height: usize) -> Option<usize> {
+        return width
+            .checked_mul(height)
+            .filter(|count| *count > 0 && *count <= MAX_PIXELS);
+    }
+
+    /// Creates an image from raw RGBA bytes e.g. of a JS `ImageData`
+    pub fn from_rgba(width: usize, height: usize, bytes: &[u8]) -> Result<Self, String> {
+        let count = Image::pixel_count(width, height)
+            .ok_or_else(|| format!("invalid image size {}x{}", width, height))?;
+        if bytes.len() != count * 4 {
+            return Err(format!(
+                "expected {} bytes for a {}x{} image, got {}",
+                count * 4,
+                width,
+                height,
+                bytes.len()
+            ));
+        }
+        let pixels = bytes
+            .chunks_exact(4)
+            .map(|rgba| RGBA::new(rgba[0], rgba[1], rgba[2], rgba[3]))
+            .collect();
+        return Image::new(width, height, pixels);
+    }
+
+    /// Get the pixel at the column and row
+    pub fn pixel(&self, x: usize, y: usize) -> RGBA {
+        return self.pixels[y * self.width + x];
+    }
+}
+
+#[derive(Debug, Clone)]
+/// An image mapped on the surface with the texture coordinates,
+/// `v` going from the bottom to the top of the image.
+/// Shapes without texture coordinates take the bottom left pixel
+pub struct ImageTexture {
+    pub image: Rc<Image>,
+    pub filter: Filter,
+    pub wrap: Wrap,
+}
+
+impl ImageTexture {
+    pub fn new(image: Rc<Image>, filter: Filter, wrap: Wrap) -> Self {
+        return ImageTexture {
+            image,
+            filter,
+            wrap,
+        };
+    }
+
+    /// Get the pixel at the column and row, wrapped into the image
+    fn texel(&self, x: i64, y: i64) -> RGBA {
+        return self.image.pixel(
+            self.wrap.index(x, self.image.width),
+            self.wrap.index(y, self.image.height),
+        );
+    }
+}
+
+impl Texture for ImageTexture {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let (u, v) = point.uv.unwrap_or((0.0, 0.0));
+        // position in pixels, the centers being at half integers
+        let x = u * self.image.width as f64;
+        let y = (1.0 - v) * self.image.height as f64;
+        if self.filter == Filter::Nearest {
+            return self.texel(x.floor() as i64, y.floor() as i64);
+        }
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let corners = [
+            (self.texel(x0, y0), (1.0 - tx) * (1.0 - ty)),
+            (self.texel(x0 + 1, y0), tx * (1.0 - ty)),
+            (self.texel(x0, y0 + 1), (1.0 - tx) * ty),
+            (self.texel(x0 + 1, y0 + 1), tx * ty),
+        ];
+        // blend every channels in floating point before rounding once
+        let mut channels = [0.0; 4];
+        for (texel, weight) in corners.iter() {
+            channels[0] += f64::from(texel.r) * weight;
+            channels[1] += f64::from(texel.g) * weight;
+            channels[2] += f64::from(texel.b) * weight;
+            channels[3] += f64::from(texel.a) * weight;
+        }
+        let to_u8 = |value: f64| value.round().clamp(0.0, 255.0) as u8;
+        return RGBA::new(
+            to_u8(channels[0]),
+            to_u8(channels[1]),
+            to_u8(channels[2]),
+            to_u8(channels[3]),
+        );
+    }
+}
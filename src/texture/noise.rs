@@ -0,0 +1,119 @@
+use crate::math::noise::Perlin;
+use crate::math::vec::Vec3;
+use crate::render::RGBA;
+
+use super::{mix, Texture, TexturePoint};
+
+#[derive(Debug, Clone)]
+/// Veins of a color through a base color, bent by turbulence
+pub struct Marble {
+    pub base: RGBA,
+    pub vein: RGBA,
+    // number of veins per unit along the x axis
+    pub frequency: f64,
+    // how much the veins are bent
+    pub turbulence: f64,
+    noise: Perlin,
+}
+
+impl Marble {
+    pub fn new(base: RGBA, vein: RGBA, frequency: f64, turbulence: f64, seed: u64) -> Self {
+        return Marble {
+            base,
+            vein,
+            frequency,
+            turbulence,
+            noise: Perlin::new(seed),
+        };
+    }
+}
+
+impl Texture for Marble {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let position = point.position * self.frequency;
+        let phase = position.x + self.turbulence * self.noise.turbulence(position, 6);
+        // sharp veins where the sine is close to 1
+        let t = (0.5 + 0.5 * phase.sin()).powi(4);
+        return mix(self.base, self.vein, t);
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Concentric rings around the vertical axis going through `center`,
+/// distorted by noise
+pub struct Wood {
+    pub light: RGBA,
+    pub dark: RGBA,
+    pub center: Vec3,
+    // number of rings per unit of distance to the axis
+    pub rings: f64,
+    // how much the rings are distorted
+    pub distortion: f64,
+    noise: Perlin,
+}
+
+impl Wood {
+    pub fn new(
+        light: RGBA,
+        dark: RGBA,
+        center: Vec3,
+        rings: f64,
+        distortion: f64,
+        seed: u64,
+    ) -> Self {
+        return Wood {
+            light,
+            dark,
+            center,
+            rings,
+            distortion,
+            noise: Perlin::new(seed),
+        };
+    }
+}
+
+impl Texture for Wood {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let offset = point.position - self.center;
+        let distance = (offset.x * offset.x + offset.z * offset.z).sqrt();
+        let ring = distance * self.rings + self.distortion * self.noise.fbm(offset * 2.0, 3);
+        // the dark part of every ring is the thinnest
+        let t = ring - ring.floor();
+        return mix(self.light, self.dark, t * t);
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Speckles of two colors over a base color
+pub struct Granite {
+    pub base: RGBA,
+    pub light: RGBA,
+    pub dark: RGBA,
+    // size of the speckles is about the inverse of the frequency
+    pub frequency: f64,
+    noise: Perlin,
+}
+
+impl Granite {
+    pub fn new(base: RGBA, light: RGBA, dark: RGBA, frequency: f64, seed: u64) -> Self {
+        return Granite {
+            base,
+            light,
+            dark,
+            frequency,
+            noise: Perlin::new(seed),
+        };
+    }
+}
+
+impl Texture for Granite {
+    fn color(&self, point: &TexturePoint) -> RGBA {
+        let value = self.noise.fbm(point.position * self.frequency, 4);
+        // contrasted speckles from the extremes of the noise
+        let t = (value.abs() * 3.0).clamp(0.0, 1.0);
+        if value < 0.0 {
+            return mix(self.base, self.dark, t);
+        }
+        return mix(self.base, self.light, t);
+    }
+}
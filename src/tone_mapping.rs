@@ -0,0 +1,83 @@
+use crate::render::Color;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Operator mapping HDR colors into the displayable [0, 1] range
+pub enum ToneMapping {
+    /// Clip every channel above 1.0
+    Clamp,
+    /// `c / (1 + c)`
+    Reinhard,
+    /// Reinhard mapping the white point to 1.0
+    ExtendedReinhard,
+    /// Hable "Uncharted 2" filmic curve
+    Filmic,
+    /// Narkowicz fit of the ACES filmic curve
+    Aces,
+}
+
+/// Display transform applied to every pixels before writing them to the canvas
+#[derive(Debug, Copy, Clone)]
+pub struct DisplayTransform {
+    pub operator: ToneMapping,
+    // exposure in stops, the color is scaled by 2^exposure
+    pub exposure: f64,
+    // smallest value mapped to white by the extended Reinhard operator
+    pub white_point: f64,
+}
+
+impl DisplayTransform {
+    pub fn new(operator: ToneMapping, exposure: f64, white_point: f64) -> Self {
+        DisplayTransform {
+            operator,
+            exposure,
+            white_point,
+        }
+    }
+
+    /// Expose and tone map the color, the result being in [0, 1]
+    pub fn apply(&self, color: Color) -> Color {
+        let exposed = color * 2.0_f64.powf(self.exposure);
+        let map = |value: f64| -> f64 {
+            let value = value.max(0.0);
+            let mapped = match self.operator {
+                ToneMapping::Clamp => value,
+                ToneMapping::Reinhard => value / (1.0 + value),
+                ToneMapping::ExtendedReinhard => {
+                    let white_squared = self.white_point * self.white_point;
+                    value * (1.0 + value / white_squared) / (1.0 + value)
+                }
+                ToneMapping::Filmic => {
+                    // the curve is normalized by its value at the linear white
+                    const WHITE: f64 = 11.2;
+                    const EXPOSURE_BIAS: f64 = 2.0;
+                    hable(value * EXPOSURE_BIAS) / hable(WHITE)
+                }
+                ToneMapping::Aces => {
+                    (value * (2.51 * value + 0.03)) / (value * (2.43 * value + 0.59) + 0.14)
+                }
+            };
+            return mapped.clamp(0.0, 1.0);
+        };
+        return Color::new(map(exposed.r), map(exposed.g), map(exposed.b));
+    }
+}
+
+impl Default for DisplayTransform {
+    /// Matches the plain clipping of the colors
+    fn default() -> Self {
+        DisplayTransform::new(ToneMapping::Clamp, 0.0, 4.0)
+    }
+}
+
+/// Hable "Uncharted 2" filmic curve
+fn hable(x: f64) -> f64 {
+    const A: f64 = 0.15;
+    const B: f64 = 0.50;
+    const C: f64 = 0.10;
+    const D: f64 = 0.20;
+    const E: f64 = 0.02;
+    const F: f64 = 0.30;
+    return ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F;
+}
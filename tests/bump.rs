@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use wasm::geometry::mesh::Mesh;
+    use wasm::geometry::shape::{Hit, Shape};
+    use wasm::geometry::sphere::Sphere;
+    use wasm::geometry::triangle::Triangle;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::texture::bump::{Bump, NoiseBump, NormalMap, TangentFrame};
+    use wasm::texture::image::{Filter, Image, ImageTexture, Wrap};
+    use wasm::texture::TexturePoint;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn material() -> Material {
+        Material::new(RGBA::new(255, 255, 255, 255), 0.0, 0.0)
+    }
+
+    fn assert_orthonormal(normal: Vec3, (tangent, bitangent): (Vec3, Vec3)) {
+        assert!((tangent.norm() - 1.0).abs() < EPSILON);
+        assert!((bitangent.norm() - 1.0).abs() < EPSILON);
+        assert!(tangent.dot(normal).abs() < EPSILON);
+        assert!(bitangent.dot(normal).abs() < EPSILON);
+        assert!(tangent.dot(bitangent).abs() < EPSILON);
+    }
+
+    fn frame() -> TangentFrame {
+        TangentFrame {
+            normal: Vec3::new(0.0, 0.0, 1.0),
+            tangent: Vec3::new(1.0, 0.0, 0.0),
+            bitangent: Vec3::new(0.0, 1.0, 0.0),
+        }
+    }
+
+    fn point() -> TexturePoint {
+        TexturePoint {
+            position: Vec3::new(0.3, 0.2, 0.1),
+            uv: Some((0.5, 0.5)),
+        }
+    }
+
+    #[test]
+    fn test_sphere_tangent_frame() {
+        let sphere = Sphere::new(Vec3::new(0.0, 0.0, 5.0), 1.0, material());
+        let hit = Hit::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(1.0, 0.0, 0.0), 1.0);
+        let (tangent, bitangent) = sphere.tangent_frame(&hit);
+        assert_orthonormal(sphere.normal(&hit), (tangent, bitangent));
+        // u goes around the vertical axis and v to the top pole
+        assert!((tangent.z - 1.0).abs() < EPSILON);
+        assert!((bitangent.y - 1.0).abs() < EPSILON);
+        // still defined at the pole
+        let pole = Hit::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 1.0, 0.0), 1.0);
+        assert_orthonormal(sphere.normal(&pole), sphere.tangent_frame(&pole));
+    }
+
+    #[test]
+    fn test_mesh_tangent_frame() {
+        // quad in the plane z = 2 with u along -x and v along y
+        let positions = vec![
+            Vec3::new(1.0, -1.0, 2.0),
+            Vec3::new(-1.0, -1.0, 2.0),
+            Vec3::new(-1.0, 1.0, 2.0),
+            Vec3::new(1.0, 1.0, 2.0),
+        ];
+        let uvs = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let indices = vec![[0, 1, 2], [0, 2, 3]];
+        let mesh = Mesh::new(positions, None, Some(uvs), indices, material()).unwrap();
+        let hit = mesh
+            .intersect(
+                Vec3::new(0.2, 0.3, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                10.0,
+            )
+            .unwrap();
+        let (tangent, bitangent) = mesh.tangent_frame(&hit);
+        assert_orthonormal(mesh.normal(&hit), (tangent, bitangent));
+        assert!((tangent.x + 1.0).abs() < EPSILON);
+        assert!((bitangent.y - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_triangle_tangent_frame() {
+        let triangle = Triangle::new(
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(0.0, 2.0, 2.0),
+            Vec3::new(2.0, 0.0, 2.0),
+            material(),
+        );
+        let hit = triangle
+            .intersect(
+                Vec3::new(0.5, 0.5, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                10.0,
+            )
+            .unwrap();
+        let (tangent, bitangent) = triangle.tangent_frame(&hit);
+        assert_orthonormal(triangle.normal(&hit), (tangent, bitangent));
+        assert!((tangent.y - 1.0).abs() < EPSILON);
+        assert!((bitangent.x - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_normal_map() {
+        let normal_map = |rgba: [u8; 4], strength: f64| {
+            let image = Image::from_rgba(1, 1, &rgba).unwrap();
+            NormalMap::new(
+                ImageTexture::new(Rc::new(image), Filter::Nearest, Wrap::Repeat),
+                strength,
+            )
+        };
+        // the flat color keeps the normal
+        let flat = normal_map([128, 128, 255, 255], 1.0).perturb(&point(), &frame());
+        assert!((flat.z - 1.0).abs() < 1e-4);
+        // leaning towards the tangent
+        let tilted = normal_map([255, 128, 128, 255], 1.0).perturb(&point(), &frame());
+        assert!((tilted.norm() - 1.0).abs() < EPSILON);
+        assert!(tilted.x > 0.9);
+        // no strength flattens the details
+        let flattened = normal_map([255, 128, 128, 255], 0.0).perturb(&point(), &frame());
+        assert!((flattened.z - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_noise_bump() {
+        let flat = NoiseBump::new(4.0, 0.0, 3, 1).perturb(&point(), &frame());
+        assert!((flat.z - 1.0).abs() < EPSILON);
+        let bump = NoiseBump::new(4.0, 0.1, 3, 1);
+        let normals: Vec<Vec3> = (0..50)
+            .map(|i| {
+                let position = Vec3::new(i as f64 * 0.13, i as f64 * 0.07, 0.0);
+                bump.perturb(&TexturePoint { position, uv: None }, &frame())
+            })
+            .collect();
+        assert!(normals.iter().all(|n| (n.norm() - 1.0).abs() < EPSILON));
+        assert!(normals.iter().all(|n| n.z > 0.0));
+        assert!(normals.iter().any(|n| n.z < 0.999));
+        // the same point always gets the same normal
+        let again = bump.perturb(&point(), &frame());
+        let first = bump.perturb(&point(), &frame());
+        assert_eq!((again.x, again.y, again.z), (first.x, first.y, first.z));
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use wasm::geometry::plane::Plane;
+    use wasm::geometry::shape::Shape;
+    use wasm::geometry::sphere::Sphere;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::scene::Scene;
+
+    /// Deterministic pseudo random numbers in [0, 1)
+    fn random(state: &mut u64) -> f64 {
+        *state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (*state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn random_spheres(count: usize) -> Vec<Box<dyn Shape>> {
+        let mut state = 42;
+        (0..count)
+            .map(|_| {
+                let center = Vec3::new(
+                    random(&mut state) * 20.0 - 10.0,
+                    random(&mut state) * 20.0 - 10.0,
+                    random(&mut state) * 20.0 + 5.0,
+                );
+                Box::new(Sphere::new(
+                    center,
+                    0.1 + random(&mut state) * 0.5,
+                    Material::new(RGBA::new(255, 255, 255, 255), 10.0, 0.0),
+                )) as Box<dyn Shape>
+            })
+            .collect()
+    }
+
+    /// Closest distance by testing every shapes
+    fn brute_force(shapes: &[Box<dyn Shape>], origin: Vec3, direction: Vec3) -> Option<f64> {
+        shapes
+            .iter()
+            .filter_map(|shape| shape.intersect(origin, direction, 0.0, 1000.0))
+            .map(|hit| hit.distance)
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    fn rays(count: usize) -> Vec<Vec3> {
+        let mut state = 7;
+        (0..count)
+            .map(|_| Vec3::new(random(&mut state) - 0.5, random(&mut state) - 0.5, 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_scene_matches_linear_scan() {
+        let mut shapes = random_spheres(500);
+        shapes.push(Box::new(Plane::new(
+            Vec3::new(0.0, 1.0, 0.0),
+            10.0,
+            Material::new(RGBA::new(255, 255, 255, 255), 10.0, 0.0),
+        )));
+        let reference = random_spheres(500);
+        let scene = Scene::new(shapes);
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        for direction in rays(500) {
+            let expected = brute_force(&reference, origin, direction);
+            let plane = (-10.0 - origin.y) / direction.y;
+            let expected = match expected {
+                Some(distance) if plane <= 0.0 || distance < plane => Some(distance),
+                _ if plane > 0.0 && plane < 1000.0 => Some(plane),
+                _ => None,
+            };
+            let result = scene
+                .find_intersection(origin, direction, 0.0, 1000.0)
+                .map(|(hit, _)| hit.distance);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_scene_refit_after_translate() {
+        let mut scene = Scene::new(random_spheres(200));
+        let mut reference = random_spheres(200);
+        let offset = Vec3::new(3.0, -2.0, 1.0);
+        for (index, shape) in reference.iter_mut().enumerate().take(50) {
+            scene.translate(index, offset);
+            shape.translate(offset);
+        }
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        for direction in rays(500) {
+            let expected = brute_force(&reference, origin, direction);
+            let result = scene
+                .find_intersection(origin, direction, 0.0, 1000.0)
+                .map(|(hit, _)| hit.distance);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_scene_is_occluded() {
+        let scene = Scene::new(random_spheres(500));
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        for direction in rays(500) {
+            let opt_hit = scene.find_intersection(origin, direction, 0.0, 1000.0);
+            assert_eq!(
+                scene.is_occluded(origin, direction, 0.0, 1000.0),
+                opt_hit.is_some()
+            );
+            // nothing is occluding before the closest hit
+            if let Some((hit, _)) = opt_hit {
+                assert!(!scene.is_occluded(origin, direction, 0.0, hit.distance * 0.999));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scene_empty() {
+        let scene = Scene::new(Vec::new());
+        assert!(scene
+            .find_intersection(
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                1000.0
+            )
+            .is_none());
+        assert!(!scene.is_occluded(
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            0.0,
+            1000.0
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    use wasm::camera::Camera;
+    use wasm::math::vec::Vec3;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn assert_close(a: Vec3, b: Vec3) {
+        assert!((a - b).norm() < EPSILON, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_camera_default_basis() {
+        let camera = Camera::new(Vec3::new(1.0, 2.0, 3.0));
+        let (right, up, forward) = camera.basis();
+        assert_close(right, Vec3::new(1.0, 0.0, 0.0));
+        assert_close(up, Vec3::new(0.0, 1.0, 0.0));
+        assert_close(forward, Vec3::new(0.0, 0.0, 1.0));
+        let direction = Vec3::new(0.3, -0.2, 1.0);
+        assert_close(camera.to_world(direction), direction);
+    }
+
+    #[test]
+    fn test_camera_yaw_pitch_roll() {
+        let mut camera = Camera::new(Vec3::new(0.0, 0.0, 0.0));
+        // turning right looks along +X
+        camera.yaw = FRAC_PI_2;
+        let (right, _, forward) = camera.basis();
+        assert_close(forward, Vec3::new(1.0, 0.0, 0.0));
+        assert_close(right, Vec3::new(0.0, 0.0, -1.0));
+        // looking straight up keeps a defined basis
+        camera.yaw = 0.0;
+        camera.pitch = FRAC_PI_2;
+        let (right, up, forward) = camera.basis();
+        assert_close(forward, Vec3::new(0.0, 1.0, 0.0));
+        assert_close(right, Vec3::new(1.0, 0.0, 0.0));
+        assert_close(up, Vec3::new(0.0, 0.0, -1.0));
+        // rolling keeps the view direction
+        camera.pitch = 0.0;
+        camera.roll = FRAC_PI_2;
+        let (right, up, forward) = camera.basis();
+        assert_close(forward, Vec3::new(0.0, 0.0, 1.0));
+        assert_close(right, Vec3::new(0.0, 1.0, 0.0));
+        assert_close(up, Vec3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_camera_look_at() {
+        let position = Vec3::new(1.0, 1.0, 1.0);
+        let target = Vec3::new(4.0, -1.0, 7.0);
+        let camera = Camera::look_at(position, target, Vec3::new(0.0, 1.0, 0.0));
+        let (right, up, forward) = camera.basis();
+        assert_close(forward, (target - position).normalize());
+        assert!(right.y.abs() < EPSILON);
+        assert!(up.y > 0.0);
+        assert!(camera.roll.abs() < EPSILON);
+        // a tilted up vector rolls the camera
+        let rolled = Camera::look_at(position, target, Vec3::new(1.0, 1.0, 0.0));
+        assert!(rolled.roll.abs() > 0.1);
+        assert_close(rolled.basis().2, forward);
+    }
+
+    #[test]
+    fn test_camera_move_relative() {
+        let mut camera = Camera::new(Vec3::new(0.0, 0.0, 0.0));
+        camera.yaw = FRAC_PI_2;
+        camera.move_relative(Vec3::new(0.0, 0.0, 2.0));
+        assert_close(camera.position, Vec3::new(2.0, 0.0, 0.0));
+        camera.move_relative(Vec3::new(1.0, 0.0, 0.0));
+        assert_close(camera.position, Vec3::new(2.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_camera_turn() {
+        let mut camera = Camera::new(Vec3::new(0.0, 0.0, 0.0));
+        camera.turn(0.5, 0.25);
+        assert!((camera.yaw - 0.5).abs() < EPSILON);
+        assert!((camera.pitch - 0.25).abs() < EPSILON);
+        // the pitch stops before looking straight up or down
+        camera.turn(0.0, 10.0);
+        assert!(camera.pitch < FRAC_PI_2);
+        assert!(camera.pitch > FRAC_PI_2 - 0.1);
+        camera.turn(0.0, -20.0);
+        assert!(camera.pitch > -FRAC_PI_2);
+        assert!(camera.basis().2.y < 0.0);
+        // the yaw wraps around
+        camera.turn(4.0 * PI, 0.0);
+        assert!((camera.yaw - 0.5).abs() < 1e-6);
+    }
+}
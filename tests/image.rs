@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use wasm::import::load_image;
+    use wasm::import::png::decode_png;
+    use wasm::import::ppm::decode_ppm;
+    use wasm::import::Format;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::texture::image::{Filter, Image, ImageTexture, Wrap};
+    use wasm::texture::{Texture, TexturePoint};
+
+    const RGBA_PNG: &[u8] = include_bytes!("data/rgba.png");
+    const RGBA_STORED_PNG: &[u8] = include_bytes!("data/rgba_stored.png");
+    const RGBA_FIXED_PNG: &[u8] = include_bytes!("data/rgba_fixed.png");
+    const RGB_INTERLACED_PNG: &[u8] = include_bytes!("data/rgb_interlaced.png");
+    const GRAY16_PNG: &[u8] = include_bytes!("data/gray16.png");
+    const PALETTE2_PNG: &[u8] = include_bytes!("data/palette2.png");
+    // 4x4 gray RGB images with exactly, less and far more image data than needed
+    const EXACT_IDAT_PNG: &[u8] = include_bytes!("data/exact_idat.png");
+    const SHORT_IDAT_PNG: &[u8] = include_bytes!("data/short_idat.png");
+    const LONG_IDAT_PNG: &[u8] = include_bytes!("data/long_idat.png");
+    // 2147483647x2147483647 image
+    const HUGE_PNG: &[u8] = include_bytes!("data/huge.png");
+
+    /// Pattern of the RGBA test images
+    fn pattern(x: usize, y: usize) -> RGBA {
+        RGBA::new(
+            ((x * 37 + y * 11) % 256) as u8,
+            ((x * y * 5) % 256) as u8,
+            ((255 + 256 - (x * 13) % 256) % 256) as u8,
+            (((x + y) * 20) % 256) as u8,
+        )
+    }
+
+    fn point(u: f64, v: f64) -> TexturePoint {
+        TexturePoint {
+            position: Vec3::new(0.0, 0.0, 0.0),
+            uv: Some((u, v)),
+        }
+    }
+
+    /// 2x2 image of black, red / green, white from the top left corner
+    fn quad() -> Rc<Image> {
+        Rc::new(
+            Image::from_rgba(
+                2,
+                2,
+                &[
+                    0, 0, 0, 255, 255, 0, 0, 255, //
+                    0, 255, 0, 255, 255, 255, 255, 255,
+                ],
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_decode_png_rgba() {
+        for bytes in [RGBA_PNG, RGBA_STORED_PNG, RGBA_FIXED_PNG] {
+            let image = decode_png(bytes).unwrap();
+            assert_eq!((image.width, image.height), (32, 24));
+            for y in 0..image.height {
+                for x in 0..image.width {
+                    assert_eq!(image.pixel(x, y), pattern(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_png_interlaced() {
+        let image = decode_png(RGB_INTERLACED_PNG).unwrap();
+        assert_eq!((image.width, image.height), (13, 11));
+        for y in 0..image.height {
+            for x in 0..image.width {
+                let expected = pattern(x, y);
+                assert_eq!(
+                    image.pixel(x, y),
+                    RGBA::new(expected.r, expected.g, expected.b, 255)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_png_gray_and_palette() {
+        let gray = decode_png(GRAY16_PNG).unwrap();
+        let sample: u32 = 4 * 7001 + 2 * 333;
+        let value = (sample * 255 / 65535) as u8;
+        assert_eq!(gray.pixel(4, 2), RGBA::new(value, value, value, 255));
+        let palette = decode_png(PALETTE2_PNG).unwrap();
+        assert_eq!((palette.width, palette.height), (10, 6));
+        assert_eq!(palette.pixel(0, 0), RGBA::new(255, 0, 0, 0));
+        assert_eq!(palette.pixel(1, 0), RGBA::new(0, 255, 0, 128));
+        assert_eq!(palette.pixel(2, 0), RGBA::new(0, 0, 255, 255));
+        assert_eq!(palette.pixel(9, 5), RGBA::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn test_decode_png_errors() {
+        assert!(decode_png(b"not a png").is_err());
+        let mut corrupted = RGBA_PNG.to_vec();
+        corrupted[40] ^= 0xff;
+        let error = decode_png(&corrupted).err().unwrap();
+        assert_eq!(error.format, Format::Png);
+        assert!(error.to_string().starts_with("png: "));
+        assert!(decode_png(&RGBA_PNG[..RGBA_PNG.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn test_decode_png_data_size() {
+        let image = decode_png(EXACT_IDAT_PNG).unwrap();
+        assert_eq!(image.pixel(3, 3), RGBA::new(128, 128, 128, 255));
+        let error = decode_png(SHORT_IDAT_PNG).err().unwrap();
+        assert_eq!(error.to_string(), "png: not enough image data");
+        // decompressing stops at the size given by the header
+        let error = decode_png(LONG_IDAT_PNG).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "png: decompressed data larger than expected"
+        );
+        let error = decode_png(HUGE_PNG).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "png: invalid image size 2147483647x2147483647"
+        );
+    }
+
+    #[test]
+    fn test_decode_ppm() {
+        let ascii = decode_ppm(b"P3\n# a comment\n2 1\n255\n255 0 0   0 128 255\n").unwrap();
+        assert_eq!(ascii.pixel(0, 0), RGBA::new(255, 0, 0, 255));
+        assert_eq!(ascii.pixel(1, 0), RGBA::new(0, 128, 255, 255));
+        let mut binary = b"P6 1 2 15\n".to_vec();
+        binary.extend_from_slice(&[15, 0, 5, 0, 15, 0]);
+        let binary = load_image(&binary).unwrap();
+        assert_eq!(binary.pixel(0, 0), RGBA::new(255, 0, 85, 255));
+        assert_eq!(binary.pixel(0, 1), RGBA::new(0, 255, 0, 255));
+        let error = decode_ppm(b"P3\n1 1\n255\n0 0").err().unwrap();
+        assert_eq!(error.to_string(), "ppm line 4: missing sample");
+        assert!(decode_ppm(b"P3 1 1 255 0 300 0").is_err());
+        assert!(load_image(b"GIF89a").is_err());
+    }
+
+    #[test]
+    fn test_decode_ppm_size() {
+        let error = load_image(b"P6 4294967296 4294967296 255\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "ppm line 1: invalid image size 4294967296x4294967296"
+        );
+        assert!(decode_ppm(b"P3 0 1 255").is_err());
+        assert!(decode_ppm(b"P6 4097 4096 255\n").is_err());
+    }
+
+    #[test]
+    fn test_image_from_rgba() {
+        assert_eq!(
+            Image::from_rgba(2, 2, &[0; 15]).err().unwrap(),
+            "expected 16 bytes for a 2x2 image, got 15"
+        );
+        // 32768 × 32768 × 4 wraps to 0 on 32 bits targets
+        assert!(Image::from_rgba(32768, 32768, &[]).is_err());
+        assert!(Image::from_rgba(usize::MAX, 2, &[]).is_err());
+        assert!(Image::from_rgba(0, 2, &[]).is_err());
+        let image = quad();
+        assert_eq!(image.pixel(1, 0), RGBA::new(255, 0, 0, 255));
+        assert_eq!(image.pixel(0, 1), RGBA::new(0, 255, 0, 255));
+    }
+
+    #[test]
+    fn test_image_pixel_count() {
+        assert_eq!(Image::pixel_count(4096, 4096), Some(4096 * 4096));
+        assert_eq!(Image::pixel_count(4096, 4097), None);
+        assert_eq!(Image::pixel_count(0, 4), None);
+        assert_eq!(Image::pixel_count(usize::MAX, usize::MAX), None);
+    }
+
+    #[test]
+    fn test_image_new() {
+        let black = RGBA::new(0, 0, 0, 255);
+        assert!(Image::new(2, 2, vec![black; 4]).is_ok());
+        assert_eq!(
+            Image::new(2, 2, vec![black; 3]).err().unwrap(),
+            "expected 4 pixels for a 2x2 image, got 3"
+        );
+        assert_eq!(
+            Image::new(0, 0, Vec::new()).err().unwrap(),
+            "invalid image size 0x0"
+        );
+        assert!(Image::new(4097, 4096, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_image_texture_nearest() {
+        let texture = ImageTexture::new(quad(), Filter::Nearest, Wrap::Repeat);
+        // v goes from the bottom to the top of the image
+        assert_eq!(texture.color(&point(0.25, 0.75)), RGBA::new(0, 0, 0, 255));
+        assert_eq!(texture.color(&point(0.75, 0.75)), RGBA::new(255, 0, 0, 255));
+        assert_eq!(texture.color(&point(0.25, 0.25)), RGBA::new(0, 255, 0, 255));
+        assert_eq!(
+            texture.color(&point(1.75, -0.75)),
+            RGBA::new(255, 255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_image_texture_wrap() {
+        let repeat = ImageTexture::new(quad(), Filter::Nearest, Wrap::Repeat);
+        let clamp = ImageTexture::new(quad(), Filter::Nearest, Wrap::Clamp);
+        let mirror = ImageTexture::new(quad(), Filter::Nearest, Wrap::Mirror);
+        // one pixel right of the image
+        let outside = point(1.25, 0.75);
+        assert_eq!(repeat.color(&outside), RGBA::new(0, 0, 0, 255));
+        assert_eq!(clamp.color(&outside), RGBA::new(255, 0, 0, 255));
+        assert_eq!(mirror.color(&outside), RGBA::new(255, 0, 0, 255));
+        // one pixel left of the image
+        let outside = point(-0.25, 0.75);
+        assert_eq!(repeat.color(&outside), RGBA::new(255, 0, 0, 255));
+        assert_eq!(clamp.color(&outside), RGBA::new(0, 0, 0, 255));
+        assert_eq!(mirror.color(&outside), RGBA::new(0, 0, 0, 255));
+    }
+
+    #[test]
+    fn test_image_texture_bilinear() {
+        let texture = ImageTexture::new(quad(), Filter::Bilinear, Wrap::Clamp);
+        // pixel centers are exact
+        assert_eq!(texture.color(&point(0.75, 0.75)), RGBA::new(255, 0, 0, 255));
+        // halfway between the four pixels
+        assert_eq!(
+            texture.color(&point(0.5, 0.5)),
+            RGBA::new(128, 128, 64, 255)
+        );
+        // halfway between black and red
+        assert_eq!(texture.color(&point(0.5, 0.75)), RGBA::new(128, 0, 0, 255));
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod tests {
+    use wasm::geometry::light::{
+        AreaShape, Attenuation, Light, LightAmbient, LightArea, LightComputeInfo, LightDirectional,
+        LightPoint, LightSpot,
+    };
+    use wasm::geometry::shape::Shape;
+    use wasm::geometry::sphere::Sphere;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::{srgb_to_linear, Color, RGBA};
+    use wasm::scene::{Scene, ShadowBias};
+
+    fn material() -> Material {
+        Material::new(RGBA::new(255, 255, 255, 255), -1.0, 0.0)
+    }
+
+    fn occluder(center: Vec3, radius: f64) -> Box<dyn Shape> {
+        Box::new(Sphere::new(center, radius, material()))
+    }
+
+    /// A point at the origin of a surface facing up
+    fn info() -> LightComputeInfo {
+        LightComputeInfo {
+            direction: Vec3::new(0.0, -1.0, 0.0),
+            normal: Vec3::new(0.0, 1.0, 0.0),
+            position: Vec3::new(0.0, 0.0, 0.0),
+            is_diffuse: true,
+            is_specular: false,
+            is_shadow: true,
+        }
+    }
+
+    #[test]
+    fn test_point_light_occluder_in_front_casts_shadow() {
+        let scene = Scene::new(vec![occluder(Vec3::new(0.0, 1.0, 0.0), 0.5)]);
+        let light = LightPoint::new(0.6, Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(light.compute(&info(), &material(), &scene), Color::black());
+    }
+
+    #[test]
+    fn test_point_light_occluder_behind_casts_no_shadow() {
+        let scene = Scene::new(vec![occluder(Vec3::new(0.0, 5.0, 0.0), 1.0)]);
+        let light = LightPoint::new(0.6, Vec3::new(0.0, 2.0, 0.0));
+        assert!((light.compute(&info(), &material(), &scene).g - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_directional_light_shadow() {
+        let scene = Scene::new(vec![occluder(Vec3::new(0.0, 50.0, 0.0), 1.0)]);
+        let light = LightDirectional::new(0.2, Vec3::new(0.0, 4.0, 0.0));
+        assert_eq!(light.compute(&info(), &material(), &scene), Color::black());
+        let light = LightDirectional::new(0.2, Vec3::new(0.0, 4.0, 4.0));
+        assert!(light.compute(&info(), &material(), &scene).r > 0.0);
+    }
+
+    #[test]
+    fn test_light_behind_surface() {
+        let scene = Scene::new(Vec::new());
+        let below = Vec3::new(0.0, -2.0, 0.0);
+        let lights: Vec<Box<dyn Light>> = vec![
+            Box::new(LightPoint::new(0.6, below)),
+            Box::new(LightDirectional::new(0.6, below)),
+            Box::new(LightSpot::new(
+                0.6,
+                below,
+                Vec3::new(0.0, 1.0, 0.0),
+                0.5,
+                1.0,
+            )),
+            Box::new(LightArea::new(
+                0.6,
+                below,
+                AreaShape::Sphere { radius: 0.5 },
+                16,
+            )),
+        ];
+        for is_shadow in [true, false] {
+            let info = LightComputeInfo {
+                is_shadow,
+                ..info()
+            };
+            for light in lights.iter() {
+                assert_eq!(light.compute(&info, &material(), &scene), Color::black());
+            }
+        }
+    }
+
+    #[test]
+    fn test_spot_light_cone() {
+        let scene = Scene::new(Vec::new());
+        // pointing down on the origin
+        let angle = |degrees: f64| degrees.to_radians();
+        let spot = |x: f64| {
+            LightSpot::new(
+                1.0,
+                Vec3::new(x, 1.0, 0.0),
+                Vec3::new(0.0, -1.0, 0.0),
+                angle(20.0),
+                angle(40.0),
+            )
+        };
+        // inside the inner cone at full intensity
+        let color = spot(0.0).compute(&info(), &material(), &scene);
+        assert!((color.r - 1.0).abs() < 1e-9);
+        // between the cones, decreasing with the angle
+        let falloff = |degrees: f64| {
+            let to_position = Vec3::new(angle(degrees).tan(), -1.0, 0.0);
+            spot(0.0).falloff(&to_position)
+        };
+        assert_eq!(falloff(15.0), 1.0);
+        assert!(falloff(25.0) < 1.0);
+        assert!(falloff(35.0) < falloff(25.0));
+        assert!(falloff(35.0) > 0.0);
+        assert_eq!(falloff(45.0), 0.0);
+        // outside the outer cone
+        let color = spot(2.0).compute(&info(), &material(), &scene);
+        assert_eq!(color, Color::black());
+    }
+
+    #[test]
+    fn test_spot_light_shadow() {
+        let scene = Scene::new(vec![occluder(Vec3::new(0.0, 1.0, 0.0), 0.5)]);
+        let spot = LightSpot::new(
+            1.0,
+            Vec3::new(0.0, 2.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            0.5,
+            0.6,
+        );
+        assert_eq!(spot.compute(&info(), &material(), &scene), Color::black());
+    }
+
+    #[test]
+    fn test_area_light_penumbra() {
+        let shapes = [
+            AreaShape::Rectangle {
+                edge_u: Vec3::new(2.0, 0.0, 0.0),
+                edge_v: Vec3::new(0.0, 0.0, 2.0),
+            },
+            AreaShape::Disk {
+                normal: Vec3::new(0.0, -1.0, 0.0),
+                radius: 1.0,
+            },
+            AreaShape::Sphere { radius: 1.0 },
+        ];
+        for shape in shapes {
+            let light = LightArea::new(1.0, Vec3::new(0.0, 4.0, 0.0), shape, 64);
+            // fully lit
+            let scene = Scene::new(Vec::new());
+            let full = light.compute(&info(), &material(), &scene).r;
+            assert!(full > 0.9 && full <= 1.0);
+            // an occluder covering half of the light
+            let scene = Scene::new(vec![occluder(Vec3::new(-1.0, 2.0, 0.0), 1.0)]);
+            let half = light.compute(&info(), &material(), &scene).r;
+            assert!(half > 0.2 * full && half < 0.8 * full);
+            // an occluder covering the whole light
+            let scene = Scene::new(vec![occluder(Vec3::new(0.0, 2.0, 0.0), 1.5)]);
+            assert_eq!(light.compute(&info(), &material(), &scene), Color::black());
+        }
+    }
+
+    #[test]
+    fn test_attenuation() {
+        assert_eq!(Attenuation::None.factor(4.0), 1.0);
+        assert_eq!(Attenuation::Linear.factor(4.0), 0.25);
+        assert_eq!(Attenuation::InverseSquare.factor(4.0), 1.0 / 16.0);
+        let coefficients = Attenuation::Coefficients {
+            constant: 1.0,
+            linear: 0.5,
+            quadratic: 0.25,
+        };
+        assert_eq!(coefficients.factor(2.0), 1.0 / 3.0);
+        // far lights are dimmer
+        let scene = Scene::new(Vec::new());
+        let light = |height: f64| {
+            LightPoint::new(1.0, Vec3::new(0.0, height, 0.0))
+                .with_attenuation(Attenuation::InverseSquare)
+                .compute(&info(), &material(), &scene)
+        };
+        assert_eq!(light(2.0).r, 0.25);
+        assert_eq!(light(4.0).r, 1.0 / 16.0);
+    }
+
+    #[test]
+    fn test_light_color() {
+        let scene = Scene::new(Vec::new());
+        // sRGB decoded as the lights of the raytracer
+        let light = LightAmbient::new(0.5).with_color(scene.color(RGBA::from_hex(0xff8000)));
+        let color = light.compute(&info(), &material(), &scene);
+        assert_eq!(color.r, 0.5);
+        assert!((color.g - 0.5 * srgb_to_linear(128.0 / 255.0)).abs() < 1e-9);
+        assert_eq!(color.b, 0.0);
+        let light =
+            LightPoint::new(1.0, Vec3::new(0.0, 2.0, 0.0)).with_color(Color::new(1.0, 0.5, 0.25));
+        let color = light.compute(&info(), &material(), &scene);
+        assert_eq!(color, Color::new(1.0, 0.5, 0.25));
+    }
+
+    #[test]
+    fn test_shadow_bias_on_large_sphere() {
+        // a point on top of a large sphere lit at a grazing angle
+        let radius = 5000.0;
+        let mut scene = Scene::new(vec![occluder(Vec3::new(0.0, -radius, 0.0), radius)]);
+        let position = Vec3::new(0.0, 0.0, 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let direction = Vec3::new(1.0, 0.01, 0.0).normalize();
+        for bias in [ShadowBias::NormalOffset(0.001), ShadowBias::Distance(0.001)] {
+            scene.shadow_bias = bias;
+            assert!(!scene.is_shadowed(position, normal, direction, 10.0));
+        }
+        // the light below the horizon is hidden by the sphere itself
+        let direction = Vec3::new(1.0, -0.01, 0.0).normalize();
+        assert!(scene.is_shadowed(position, normal, direction, 1000.0));
+    }
+}
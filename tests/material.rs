@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use wasm::material::{fresnel_dielectric, fresnel_schlick, Fresnel, Material};
+    use wasm::render::{Color, RGBA};
+    use wasm::scene::Scene;
+
+    const GLASS: f64 = 1.5;
+
+    #[test]
+    fn test_fresnel_normal_incidence() {
+        // ((n1 - n2) / (n1 + n2))² = 0.04 for glass
+        let eta = 1.0 / GLASS;
+        assert!((fresnel_dielectric(1.0, eta) - 0.04).abs() < 1e-9);
+        assert!((fresnel_schlick(1.0, eta, 0.04) - 0.04).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fresnel_grazing_angle() {
+        let eta = 1.0 / GLASS;
+        let mut previous = 0.0;
+        for step in 0..=10 {
+            let cos = 1.0 - step as f64 / 10.0;
+            let reflectance = fresnel_dielectric(cos, eta);
+            assert!(reflectance >= previous - 1e-9);
+            previous = reflectance;
+        }
+        assert!((fresnel_dielectric(0.0, eta) - 1.0).abs() < 1e-9);
+        assert!((fresnel_schlick(0.0, eta, 0.04) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fresnel_total_internal_reflection() {
+        assert_eq!(fresnel_dielectric(0.2, GLASS), 1.0);
+        assert_eq!(fresnel_schlick(0.2, GLASS, 0.04), 1.0);
+        assert!(fresnel_dielectric(1.0, GLASS) < 0.1);
+    }
+
+    #[test]
+    fn test_material_reflectance() {
+        let color = RGBA::new(255, 255, 255, 255);
+        let constant = Material::new(color, 10.0, 0.3);
+        assert_eq!(constant.reflectance(1.0, 1.0), 0.3);
+        assert_eq!(constant.reflectance(0.1, 1.0), 0.3);
+        let schlick = Material::new(color, 10.0, 0.3).with_fresnel(Fresnel::Schlick);
+        assert!((schlick.reflectance(1.0, 1.0) - 0.3).abs() < 1e-9);
+        assert!(schlick.reflectance(0.1, 1.0) > 0.5);
+        let glass = Material::new(color, 10.0, 0.0)
+            .with_refraction(1.0, GLASS)
+            .with_fresnel(Fresnel::Schlick);
+        assert!((glass.reflectance(1.0, 1.0 / GLASS) - 0.04).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_material_emission() {
+        let mut scene = Scene::new(Vec::new());
+        scene.is_srgb = false;
+        let color = RGBA::new(255, 255, 255, 255);
+        let material = Material::new(color, 10.0, 0.0);
+        assert!(!material.is_emissive());
+        assert_eq!(material.emitted(&scene), Color::black());
+        let glowing = material.with_emission(RGBA::new(255, 0, 51, 255), 2.0);
+        assert!(glowing.is_emissive());
+        let emitted = glowing.emitted(&scene);
+        assert_eq!(emitted.r, 2.0);
+        assert_eq!(emitted.g, 0.0);
+        assert!((emitted.b - 0.4).abs() < 1e-9);
+    }
+}
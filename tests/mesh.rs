@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use wasm::geometry::mesh::Mesh;
+    use wasm::geometry::shape::Shape;
+    use wasm::geometry::triangle::Triangle;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn material() -> Material {
+        Material::new(RGBA::new(255, 255, 255, 255), 10.0, 0.0)
+    }
+
+    /// A unit square at z = 2 made of two triangles facing the origin
+    fn square() -> Mesh {
+        Mesh::new(
+            vec![
+                Vec3::new(-1.0, -1.0, 2.0),
+                Vec3::new(-1.0, 1.0, 2.0),
+                Vec3::new(1.0, 1.0, 2.0),
+                Vec3::new(1.0, -1.0, 2.0),
+            ],
+            None,
+            Some(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]),
+            vec![[0, 1, 2], [0, 2, 3]],
+            material(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_triangle_intersect() {
+        let triangle = Triangle::new(
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 1.0, 1.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            material(),
+        );
+        let origin = Vec3::new(0.25, 0.25, 0.0);
+        let hit = triangle
+            .intersect(origin, Vec3::new(0.0, 0.0, 1.0), 0.0, 100.0)
+            .unwrap();
+        assert!((hit.distance - 1.0).abs() < EPSILON);
+        assert!((hit.barycentric.0 - 0.25).abs() < EPSILON);
+        assert!((hit.barycentric.1 - 0.25).abs() < EPSILON);
+        assert_eq!(triangle.normal(&hit).z, -1.0);
+        // outside of the triangle
+        assert!(triangle
+            .intersect(
+                Vec3::new(0.75, 0.75, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                100.0
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_mesh_intersect() {
+        let mesh = square();
+        let hit = mesh
+            .intersect(
+                Vec3::new(0.5, -0.5, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                100.0,
+            )
+            .unwrap();
+        assert!((hit.distance - 2.0).abs() < EPSILON);
+        assert_eq!(hit.primitive, 1);
+        let (u, v) = mesh.uv(&hit).unwrap();
+        assert!((u - 0.75).abs() < EPSILON);
+        assert!((v - 0.25).abs() < EPSILON);
+        assert!((mesh.normal(&hit).z + 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn test_mesh_new_errors() {
+        let positions = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ];
+        let error = Mesh::new(positions.clone(), None, None, vec![[0, 1, 3]], material())
+            .err()
+            .unwrap();
+        assert_eq!(error, "vertex index 3 out of range of 3 positions");
+        let normals = vec![Vec3::new(0.0, 0.0, 1.0); 2];
+        assert!(Mesh::new(
+            positions.clone(),
+            Some(normals),
+            None,
+            vec![[0, 1, 2]],
+            material()
+        )
+        .is_err());
+        let uvs = vec![(0.0, 0.0); 4];
+        assert!(Mesh::new(positions, None, Some(uvs), vec![[0, 1, 2]], material()).is_err());
+    }
+
+    #[test]
+    fn test_mesh_smooth_normals() {
+        // two triangles folded along the y axis
+        let mut mesh = Mesh::new(
+            vec![
+                Vec3::new(0.0, -1.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(-1.0, 0.0, 1.0),
+                Vec3::new(1.0, 0.0, 1.0),
+            ],
+            None,
+            None,
+            vec![[0, 1, 2], [0, 3, 1]],
+            material(),
+        )
+        .unwrap();
+        mesh.compute_normals();
+        let normals = mesh.normals.as_ref().unwrap();
+        // shared vertices average both faces
+        assert!(normals[0].x.abs() < EPSILON);
+        assert!((normals[0].z - 1.0).abs() < EPSILON);
+        // a hit on the shared edge is smooth
+        let hit = mesh
+            .intersect(
+                Vec3::new(-0.001, 0.0, -1.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                100.0,
+            )
+            .unwrap();
+        assert!(mesh.normal(&hit).x.abs() < 0.01);
+        assert!(mesh.face_normal(hit.primitive).x.abs() > 0.5);
+    }
+
+    #[test]
+    fn test_mesh_bounding_box() {
+        let mut mesh = square();
+        mesh.translate(Vec3::new(0.0, 0.0, 1.0));
+        let aabb = mesh.bounding_box();
+        assert_eq!(aabb.min.x, -1.0);
+        assert_eq!(aabb.max.y, 1.0);
+        assert_eq!(aabb.min.z, 3.0);
+        assert_eq!(aabb.max.z, 3.0);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use wasm::math::noise::Perlin;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::texture::noise::{Granite, Marble, Wood};
+    use wasm::texture::{Texture, TexturePoint};
+
+    /// Points spread over a few lattice cells, including negative ones
+    fn points() -> Vec<Vec3> {
+        (0..200)
+            .map(|i| {
+                let i = i as f64;
+                Vec3::new(i * 0.173 - 17.0, i * 0.291 - 29.0, i * 0.037 + 3.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_noise_deterministic() {
+        let noise = Perlin::new(42);
+        let same = Perlin::new(42);
+        let other = Perlin::new(43);
+        let mut differences = 0;
+        for point in points() {
+            assert_eq!(noise.noise(point), same.noise(point));
+            if noise.noise(point) != other.noise(point) {
+                differences += 1;
+            }
+        }
+        assert!(differences > 100);
+    }
+
+    #[test]
+    fn test_noise_range_and_lattice() {
+        let noise = Perlin::new(1);
+        let values: Vec<f64> = points().into_iter().map(|p| noise.noise(p)).collect();
+        assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+        // not constant
+        assert!(values.iter().any(|v| v.abs() > 0.1));
+        assert_eq!(noise.noise(Vec3::new(3.0, -2.0, 7.0)), 0.0);
+    }
+
+    #[test]
+    fn test_noise_continuous() {
+        let noise = Perlin::new(5);
+        let delta = Vec3::new(1e-4, 1e-4, 1e-4);
+        for point in points() {
+            assert!((noise.noise(point) - noise.noise(point + delta)).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_fbm_and_turbulence() {
+        let noise = Perlin::new(9);
+        for point in points() {
+            assert!((-1.0..=1.0).contains(&noise.fbm(point, 5)));
+            assert!((0.0..=1.0).contains(&noise.turbulence(point, 5)));
+        }
+        // a single octave is the noise itself
+        let point = Vec3::new(0.3, 0.7, 0.1);
+        assert_eq!(noise.fbm(point, 1), noise.noise(point));
+        assert_eq!(noise.turbulence(point, 1), noise.noise(point).abs());
+    }
+
+    #[test]
+    fn test_noise_textures() {
+        let white = RGBA::new(255, 255, 255, 255);
+        let black = RGBA::new(0, 0, 0, 255);
+        let textures: Vec<Box<dyn Texture>> = vec![
+            Box::new(Marble::new(white, black, 2.0, 4.0, 3)),
+            Box::new(Wood::new(
+                white,
+                black,
+                Vec3::new(0.0, 0.0, 0.0),
+                4.0,
+                0.5,
+                3,
+            )),
+            Box::new(Granite::new(
+                RGBA::new(128, 128, 128, 255),
+                white,
+                black,
+                8.0,
+                3,
+            )),
+        ];
+        for texture in textures.iter() {
+            let colors: Vec<RGBA> = points()
+                .into_iter()
+                .map(|position| texture.color(&TexturePoint { position, uv: None }))
+                .collect();
+            // gray levels varying over the surface
+            assert!(colors.iter().all(|c| c.r == c.g && c.g == c.b));
+            let min = colors.iter().map(|c| c.r).min().unwrap();
+            let max = colors.iter().map(|c| c.r).max().unwrap();
+            assert!(max - min > 100);
+        }
+    }
+}
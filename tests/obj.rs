@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use wasm::import::mtl::parse_mtl;
+    use wasm::import::obj::load_obj;
+    use wasm::import::Format;
+
+    const MTL: &str = "
+# two materials
+newmtl red
+Kd 1.0 0.0 0.0
+Ns 250
+illum 2
+
+newmtl mirror
+Kd 0.5 0.5 0.5
+Ks 0.8 0.8 0.8
+illum 3
+
+newmtl glass
+Kd 1.0 1.0 1.0
+Ni 1.5
+d 0.25
+illum 7
+
+newmtl lamp
+Kd 1.0 1.0 1.0
+Ke 4.0 2.0 0.0
+";
+
+    const OBJ: &str = "
+mtllib scene.mtl
+o quad
+v -1 -1 2
+v -1 1 2
+v 1 1 2
+v 1 -1 2
+vt 0 0
+vt 0 1
+vt 1 1
+vt 1 0
+vn 0 0 -1
+usemtl red
+f 1/1/1 2/2/1 3/3/1 4/4/1
+usemtl mirror
+f -4 -2 -1
+";
+
+    #[test]
+    fn test_parse_mtl() {
+        let materials = parse_mtl(MTL).unwrap();
+        let red = &materials["red"];
+        assert_eq!(red.color.r, 255);
+        assert_eq!(red.color.g, 0);
+        assert_eq!(red.specular, 250.0);
+        assert_eq!(red.reflective, 0.0);
+        let mirror = &materials["mirror"];
+        assert_eq!(mirror.color.b, 128);
+        assert!((mirror.reflective - 0.8).abs() < 1e-9);
+        assert_eq!(mirror.transparency, 0.0);
+        let glass = &materials["glass"];
+        assert_eq!(glass.refractive_index, 1.5);
+        assert_eq!(glass.transparency, 0.75);
+        assert!(!glass.is_emissive());
+        let lamp = &materials["lamp"];
+        assert_eq!(lamp.emission_strength, 4.0);
+        assert_eq!(lamp.emission.r, 255);
+        assert_eq!(lamp.emission.g, 128);
+        assert_eq!(lamp.emission.b, 0);
+    }
+
+    #[test]
+    fn test_load_obj() {
+        let meshes = load_obj(OBJ, MTL).unwrap();
+        assert_eq!(meshes.len(), 2);
+        // the quad is triangulated and its vertices shared
+        let quad = &meshes[0];
+        assert_eq!(quad.indices.len(), 2);
+        assert_eq!(quad.positions.len(), 4);
+        assert_eq!(quad.uvs.as_ref().unwrap().len(), 4);
+        assert_eq!(quad.normals.as_ref().unwrap()[0].z, -1.0);
+        assert_eq!(quad.material.color.r, 255);
+        // negative indices are relative to the end
+        let triangle = &meshes[1];
+        assert_eq!(triangle.indices.len(), 1);
+        assert!(triangle.uvs.is_none());
+        assert!(triangle.normals.is_none());
+        assert_eq!(triangle.positions[0].x, -1.0);
+        assert_eq!(triangle.positions[2].y, -1.0);
+        assert!(triangle.material.reflective > 0.0);
+    }
+
+    #[test]
+    fn test_load_obj_errors() {
+        let error = load_obj("v 0 0 0\nv 1 0 0\nf 1 2 3\n", "").err().unwrap();
+        assert_eq!(error.format, Format::Obj);
+        assert_eq!(error.line, 3);
+        assert_eq!(
+            error.to_string(),
+            "obj line 3: vertex index 3 is out of range, 2 defined"
+        );
+        let error = load_obj("v 0 0\n", "").err().unwrap();
+        assert_eq!(error.line, 1);
+        let error = load_obj("v 0 0 a\n", "").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "obj line 1: `v` has an invalid number `a`"
+        );
+        let error = load_obj("usemtl unknown\n", "").err().unwrap();
+        assert_eq!(error.line, 1);
+        let error = load_obj("", "Kd 1 1 1\n").err().unwrap();
+        assert_eq!(error.format, Format::Mtl);
+        assert_eq!(error.line, 1);
+    }
+}
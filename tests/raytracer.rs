@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use wasm::sampling::AntiAliasing;
+    use wasm::texture::image::{Filter, Wrap};
+    use wasm::Raytracer;
+
+    const SIZE: usize = 40;
+
+    fn raytracer() -> Raytracer {
+        Raytracer::new(
+            SIZE, SIZE, 5, true, true, true, true, 3.0, 0xffffff, 0xffffff, 0xffffff,
+        )
+    }
+
+    #[test]
+    fn test_draw_supersampled() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        assert_eq!(pixels.len(), SIZE * SIZE * 4);
+        raytracer.set_anti_aliasing(AntiAliasing::Grid, 2);
+        let supersampled = raytracer.draw();
+        assert_eq!(supersampled.len(), pixels.len());
+        assert_ne!(supersampled, pixels);
+    }
+
+    #[test]
+    fn test_draw_depths() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        // a single reflected bounce by default
+        raytracer.set_recursion_depth(1);
+        assert_eq!(raytracer.draw(), pixels);
+        raytracer.set_recursion_depth(3);
+        assert_ne!(raytracer.draw(), pixels);
+        // the glass sphere is only seen through with refracted bounces
+        raytracer.set_recursion_depth(1);
+        raytracer.set_refraction_depth(0);
+        assert_ne!(raytracer.draw(), pixels);
+    }
+
+    #[test]
+    fn test_draw_adaptive_default_grid() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        // a grid of 1 still refines the edges with 2×2 samples
+        raytracer.set_anti_aliasing(AntiAliasing::Adaptive, 1);
+        let refined = raytracer.draw();
+        assert!(raytracer.refined_pixels() > 0);
+        assert_ne!(refined, pixels);
+        raytracer.set_anti_aliasing(AntiAliasing::Adaptive, 2);
+        assert_eq!(raytracer.draw(), refined);
+    }
+
+    #[test]
+    fn test_draw_adaptive_refines_edges_only() {
+        let mut raytracer = raytracer();
+        raytracer.set_anti_aliasing(AntiAliasing::Adaptive, 3);
+        raytracer.draw();
+        let refined = raytracer.refined_pixels();
+        assert!(refined > 0);
+        assert!(refined < SIZE * SIZE / 2);
+        // a lower threshold refines more pixels
+        raytracer.set_adaptive_threshold(0.0);
+        raytracer.draw();
+        assert!(raytracer.refined_pixels() > refined);
+    }
+
+    #[test]
+    fn test_draw_with_texture() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        // a red texture on the ground
+        let red = [255, 0, 0, 255].repeat(4);
+        let texture = raytracer
+            .add_texture_rgba(2, 2, &red, Filter::Bilinear, Wrap::Repeat)
+            .unwrap();
+        let png = include_bytes!("data/rgba.png");
+        assert_eq!(
+            raytracer
+                .add_texture_file(png, Filter::Nearest, Wrap::Clamp)
+                .unwrap(),
+            texture + 1
+        );
+        raytracer.set_shape_texture(0, texture).unwrap();
+        assert_ne!(raytracer.draw(), pixels);
+    }
+
+    #[test]
+    fn test_draw_camera_orientation() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        // turning around shows another part of the scene
+        raytracer.set_camera_orientation(std::f64::consts::PI, 0.0, 0.0);
+        let behind = raytracer.draw();
+        assert_ne!(behind, pixels);
+        // looking back at the scene restores the view
+        raytracer.look_at(0.0, 0.0, 10.0);
+        assert_eq!(raytracer.draw(), pixels);
+    }
+
+    #[test]
+    fn test_mouse_look() {
+        let mut raytracer = raytracer();
+        let pixels = raytracer.draw();
+        raytracer.mouse_look(100.0, -50.0, 0.002);
+        assert_ne!(raytracer.draw(), pixels);
+        // moving the mouse back restores the view
+        raytracer.mouse_look(-100.0, 50.0, 0.002);
+        assert_eq!(raytracer.draw(), pixels);
+    }
+}
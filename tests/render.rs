@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use wasm::render::{Canvas, RGBA};
+    use wasm::render::{linear_to_srgb, srgb_to_linear, Canvas, Color, RGBA};
 
     const WIDTH: usize = 400;
     const HEIGHT: usize = 600;
@@ -32,4 +32,38 @@ mod tests {
         assert_eq!(rgba_3.b, 240);
         assert_eq!(rgba_3.a, 255);
     }
+
+    #[test]
+    fn test_rgba_from_hex() {
+        assert_eq!(RGBA::from_hex(0xff8001), RGBA::new(255, 128, 1, 255));
+    }
+
+    #[test]
+    fn test_color_hdr() {
+        // channels above 1.0 are kept until quantized
+        let color = Color::from(RGBA::new(255, 128, 0, 255)) * 3.0;
+        assert_eq!(color.r, 3.0);
+        let halved = color * 0.5;
+        assert_eq!(halved.r, 1.5);
+        let rgba = (halved * 0.5).to_rgba();
+        assert_eq!(rgba.r, 191);
+        assert_eq!(rgba.g, 96);
+        assert_eq!(rgba.b, 0);
+        assert_eq!(rgba.a, 255);
+        assert_eq!((color + color).to_rgba().r, 255);
+    }
+
+    #[test]
+    fn test_srgb_round_trip() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-12);
+        // mid grey is darker in linear space
+        assert!((srgb_to_linear(0.5) - 0.214).abs() < 1e-3);
+        for value in 0..=255 {
+            let rgba = RGBA::new(value, value, value, 255);
+            let encoded = Color::from_srgb(rgba).to_srgb().to_rgba();
+            assert_eq!(encoded.r, value);
+        }
+        assert!((linear_to_srgb(2.0) - 1.0).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use wasm::math::random::Random;
+    use wasm::sampling::{sample_offsets, AntiAliasing};
+
+    const MODES: [AntiAliasing; 3] = [
+        AntiAliasing::Grid,
+        AntiAliasing::Jittered,
+        AntiAliasing::RotatedGrid,
+    ];
+
+    #[test]
+    fn test_no_anti_aliasing() {
+        let mut random = Random::new(0);
+        let offsets = sample_offsets(AntiAliasing::None, 4, &mut random);
+        assert_eq!(offsets, vec![(0.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_offsets_inside_pixel() {
+        for mode in MODES {
+            for grid_size in 1..6 {
+                let mut random = Random::new(grid_size as u64);
+                let offsets = sample_offsets(mode, grid_size, &mut random);
+                assert_eq!(offsets.len(), grid_size * grid_size);
+                for (x, y) in offsets {
+                    assert!((-0.5..0.5).contains(&x), "{:?} {}", mode, x);
+                    assert!((-0.5..0.5).contains(&y), "{:?} {}", mode, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_is_centered() {
+        let mut random = Random::new(0);
+        let offsets = sample_offsets(AntiAliasing::Grid, 2, &mut random);
+        assert_eq!(
+            offsets,
+            vec![(-0.25, -0.25), (-0.25, 0.25), (0.25, -0.25), (0.25, 0.25)]
+        );
+    }
+
+    #[test]
+    fn test_rotated_grid_distinct_rows_and_columns() {
+        let mut random = Random::new(0);
+        for n in 2..=4 {
+            let offsets = sample_offsets(AntiAliasing::RotatedGrid, n, &mut random);
+            assert_eq!(offsets.len(), n * n);
+            // N² distinct rows and columns are about 1/N² apart
+            let gap = 0.5 / (n * n) as f64;
+            for i in 0..offsets.len() {
+                for j in i + 1..offsets.len() {
+                    assert!((offsets[i].0 - offsets[j].0).abs() > gap);
+                    assert!((offsets[i].1 - offsets[j].1).abs() > gap);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_jittered_is_deterministic() {
+        let first = sample_offsets(AntiAliasing::Jittered, 3, &mut Random::new(7));
+        let second = sample_offsets(AntiAliasing::Jittered, 3, &mut Random::new(7));
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use wasm::geometry::plane::Plane;
+    use wasm::geometry::shape::Shape;
+    use wasm::geometry::sphere::Sphere;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::scene::Scene;
+
+    fn sphere(center: Vec3, radius: f64) -> Sphere {
+        Sphere::new(
+            center,
+            radius,
+            Material::new(RGBA::new(255, 255, 255, 255), 10.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_sphere_intersect() {
+        let sphere = sphere(Vec3::new(0.0, 0.0, 5.0), 1.0);
+        let hit = sphere
+            .intersect(
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                100.0,
+            )
+            .unwrap();
+        assert_eq!(hit.distance, 4.0);
+        assert_eq!(hit.position.z, 4.0);
+        let normal = sphere.normal(&hit);
+        assert_eq!(normal.z, -1.0);
+        assert_eq!(normal.norm(), 1.0);
+    }
+
+    #[test]
+    fn test_sphere_intersect_out_of_range() {
+        let sphere = sphere(Vec3::new(0.0, 0.0, 5.0), 1.0);
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        let direction = Vec3::new(0.0, 0.0, 1.0);
+        assert!(sphere.intersect(origin, direction, 0.0, 3.0).is_none());
+        assert!(sphere.intersect(origin, -direction, 0.0, 100.0).is_none());
+    }
+
+    #[test]
+    fn test_sphere_bounding_box() {
+        let sphere = sphere(Vec3::new(1.0, 2.0, 3.0), 2.0);
+        let aabb = sphere.bounding_box();
+        assert_eq!(aabb.min.x, -1.0);
+        assert_eq!(aabb.max.z, 5.0);
+        assert_eq!(aabb.center().y, 2.0);
+    }
+
+    #[test]
+    fn test_plane_intersect() {
+        // ground at y = -1
+        let mut plane = Plane::new(
+            Vec3::new(0.0, 2.0, 0.0),
+            2.0,
+            Material::new(RGBA::new(255, 255, 255, 255), 10.0, 0.0),
+        );
+        let origin = Vec3::new(0.0, 1.0, 0.0);
+        let hit = plane
+            .intersect(origin, Vec3::new(0.0, -1.0, 1.0), 0.0, 100.0)
+            .unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_eq!(hit.position.y, -1.0);
+        assert_eq!(plane.normal(&hit).y, 1.0);
+        // parallel and away from the plane
+        assert!(plane
+            .intersect(origin, Vec3::new(0.0, 0.0, 1.0), 0.0, 100.0)
+            .is_none());
+        assert!(plane
+            .intersect(origin, Vec3::new(0.0, 1.0, 0.0), 0.0, 100.0)
+            .is_none());
+        // move the ground at y = 0
+        plane.translate(Vec3::new(0.0, 1.0, 0.0));
+        let hit = plane
+            .intersect(origin, Vec3::new(0.0, -1.0, 0.0), 0.0, 100.0)
+            .unwrap();
+        assert_eq!(hit.position.y, 0.0);
+    }
+
+    #[test]
+    fn test_find_intersection_closest() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(sphere(Vec3::new(0.0, 0.0, 10.0), 1.0)),
+            Box::new(sphere(Vec3::new(0.0, 0.0, 5.0), 1.0)),
+        ];
+        let scene = Scene::new(shapes);
+        let (hit, _) = scene
+            .find_intersection(
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                0.0,
+                100.0,
+            )
+            .unwrap();
+        assert_eq!(hit.distance, 4.0);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use wasm::geometry::plane::Plane;
+    use wasm::geometry::shape::{Hit, Shape};
+    use wasm::geometry::sphere::Sphere;
+    use wasm::material::Material;
+    use wasm::math::vec::Vec3;
+    use wasm::render::RGBA;
+    use wasm::texture::{
+        mix, Checker3d, CheckerUv, Gradient, Solid, Stripes, Texture, TexturePoint,
+    };
+
+    const WHITE: RGBA = RGBA {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+    const BLACK: RGBA = RGBA {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    fn point(position: Vec3, uv: Option<(f64, f64)>) -> TexturePoint {
+        TexturePoint { position, uv }
+    }
+
+    #[test]
+    fn test_solid() {
+        let texture = Solid::new(WHITE);
+        assert_eq!(
+            texture.color(&point(Vec3::new(3.0, -2.0, 1.0), None)),
+            WHITE
+        );
+    }
+
+    #[test]
+    fn test_checker_3d() {
+        let texture = Checker3d::new(WHITE, BLACK, 2.0);
+        assert_eq!(texture.color(&point(Vec3::new(0.5, 0.5, 0.5), None)), WHITE);
+        assert_eq!(texture.color(&point(Vec3::new(2.5, 0.5, 0.5), None)), BLACK);
+        assert_eq!(texture.color(&point(Vec3::new(2.5, 2.5, 0.5), None)), WHITE);
+        // cells continue below zero
+        assert_eq!(
+            texture.color(&point(Vec3::new(-0.5, 0.5, 0.5), None)),
+            BLACK
+        );
+    }
+
+    #[test]
+    fn test_checker_uv() {
+        let texture = CheckerUv::new(WHITE, BLACK, 4.0);
+        let origin = Vec3::new(0.0, 0.0, 0.0);
+        assert_eq!(texture.color(&point(origin, Some((0.1, 0.1)))), WHITE);
+        assert_eq!(texture.color(&point(origin, Some((0.3, 0.1)))), BLACK);
+        assert_eq!(texture.color(&point(origin, Some((0.3, 0.3)))), WHITE);
+        assert_eq!(texture.color(&point(origin, None)), WHITE);
+    }
+
+    #[test]
+    fn test_stripes() {
+        let texture = Stripes::new(WHITE, BLACK, Vec3::new(2.0, 0.0, 0.0), 1.0);
+        assert_eq!(texture.color(&point(Vec3::new(0.5, 7.0, 0.0), None)), WHITE);
+        assert_eq!(
+            texture.color(&point(Vec3::new(1.5, -3.0, 0.0), None)),
+            BLACK
+        );
+        assert_eq!(texture.color(&point(Vec3::new(2.5, 0.0, 9.0), None)), WHITE);
+    }
+
+    #[test]
+    fn test_gradient() {
+        let texture = Gradient::new(
+            BLACK,
+            WHITE,
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 4.0, 0.0),
+        );
+        assert_eq!(
+            texture.color(&point(Vec3::new(1.0, -1.0, 0.0), None)),
+            BLACK
+        );
+        assert_eq!(texture.color(&point(Vec3::new(0.0, 5.0, 0.0), None)), WHITE);
+        let middle = texture.color(&point(Vec3::new(3.0, 2.0, 0.0), None));
+        assert_eq!(middle, RGBA::new(128, 128, 128, 255));
+    }
+
+    #[test]
+    fn test_mix() {
+        let color = RGBA::new(255, 17, 0, 128);
+        for t in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert_eq!(mix(color, color, t), color);
+        }
+        assert_eq!(mix(WHITE, WHITE, 0.5), WHITE);
+        assert_eq!(
+            mix(BLACK, RGBA::new(100, 200, 51, 255), 0.5),
+            RGBA::new(50, 100, 26, 255)
+        );
+    }
+
+    #[test]
+    fn test_sphere_uv() {
+        let sphere = Sphere::new(
+            Vec3::new(0.0, 0.0, 5.0),
+            1.0,
+            Material::new(WHITE, 0.0, 0.0),
+        );
+        let origin = Vec3::new(0.0, 0.0, 5.0);
+        let top = Hit::new(origin, Vec3::new(0.0, 1.0, 0.0), 1.0);
+        let (_, v) = sphere.uv(&top).unwrap();
+        assert!((v - 1.0).abs() < 1e-9);
+        let bottom = Hit::new(origin, Vec3::new(0.0, -1.0, 0.0), 1.0);
+        let (_, v) = sphere.uv(&bottom).unwrap();
+        assert!(v.abs() < 1e-9);
+        let side = Hit::new(origin, Vec3::new(1.0, 0.0, 0.0), 1.0);
+        let (u, v) = sphere.uv(&side).unwrap();
+        assert!((u - 0.5).abs() < 1e-9);
+        assert!((v - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_material_texture() {
+        let plane = Plane::new(
+            Vec3::new(0.0, 1.0, 0.0),
+            1.0,
+            Material::new(WHITE, 0.0, 0.0).with_texture(Rc::new(CheckerUv::new(WHITE, BLACK, 1.0))),
+        );
+        let mut colors = Vec::new();
+        for x in 0..4 {
+            let origin = Vec3::new(x as f64 + 0.5, 1.0, 0.5);
+            let hit = plane
+                .intersect(origin, Vec3::new(0.0, -1.0, 0.0), 0.0, 10.0)
+                .unwrap();
+            let color = plane.material().color_at(&TexturePoint {
+                position: hit.position,
+                uv: plane.uv(&hit),
+            });
+            colors.push(color);
+        }
+        // neighbor squares alternate along the floor
+        assert!(colors.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_eq!(
+            Material::new(WHITE, 0.0, 0.0).color_at(&point(Vec3::new(0.0, 0.0, 0.0), None)),
+            WHITE
+        );
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use wasm::render::Color;
+    use wasm::tone_mapping::{DisplayTransform, ToneMapping};
+
+    const OPERATORS: [ToneMapping; 5] = [
+        ToneMapping::Clamp,
+        ToneMapping::Reinhard,
+        ToneMapping::ExtendedReinhard,
+        ToneMapping::Filmic,
+        ToneMapping::Aces,
+    ];
+
+    #[test]
+    fn test_clamp_matches_clipping() {
+        let display = DisplayTransform::default();
+        let color = display.apply(Color::new(0.5, 2.0, -1.0));
+        assert_eq!(color, Color::new(0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_exposure_in_stops() {
+        let display = DisplayTransform::new(ToneMapping::Clamp, 1.0, 4.0);
+        assert_eq!(display.apply(Color::new(0.25, 0.0, 0.0)).r, 0.5);
+        let display = DisplayTransform::new(ToneMapping::Clamp, -2.0, 4.0);
+        assert_eq!(display.apply(Color::new(2.0, 0.0, 0.0)).r, 0.5);
+    }
+
+    #[test]
+    fn test_operators_are_monotonic_and_bounded() {
+        for operator in OPERATORS {
+            let display = DisplayTransform::new(operator, 0.0, 4.0);
+            let mut previous = 0.0;
+            for step in 0..100 {
+                let value = display.apply(Color::new(step as f64 * 0.2, 0.0, 0.0)).r;
+                assert!(value >= previous, "{:?} is not monotonic", operator);
+                assert!((0.0..=1.0).contains(&value));
+                previous = value;
+            }
+            assert!(display.apply(Color::black()).r.abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_reinhard() {
+        let display = DisplayTransform::new(ToneMapping::Reinhard, 0.0, 4.0);
+        assert_eq!(display.apply(Color::new(1.0, 3.0, 0.0)).r, 0.5);
+        assert_eq!(display.apply(Color::new(1.0, 3.0, 0.0)).g, 0.75);
+        // the white point is mapped to white
+        let display = DisplayTransform::new(ToneMapping::ExtendedReinhard, 0.0, 4.0);
+        assert!((display.apply(Color::new(4.0, 0.0, 0.0)).r - 1.0).abs() < 1e-9);
+    }
+}
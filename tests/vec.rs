@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use wasm::math::vec::Vec3;
+    use wasm::math::vec::{refraction, Vec3};
 
     const X: f64 = 1.0;
     const Y: f64 = 1.0;
@@ -62,4 +62,33 @@ mod tests {
         let vec = Vec3::new(X, Y, Z);
         assert_eq!(vec.normalize().norm(), 1.0_f64.sqrt());
     }
+
+    #[test]
+    fn test_refraction_straight() {
+        let direction = Vec3::new(0.0, -1.0, 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let refracted = refraction(&direction, &normal, 1.0 / 1.5).unwrap();
+        assert!((refracted - direction).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_refraction_snell() {
+        let eta = 1.0 / 1.5;
+        let direction = Vec3::new(1.0, -1.0, 0.0).normalize();
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let refracted = refraction(&direction, &normal, eta).unwrap();
+        assert!((refracted.norm() - 1.0).abs() < 1e-9);
+        // n1 sin(θ1) = n2 sin(θ2)
+        assert!((refracted.x - direction.x * eta).abs() < 1e-9);
+        assert!(refracted.y < direction.y);
+    }
+
+    #[test]
+    fn test_refraction_total_internal_reflection() {
+        // leaving glass at a grazing angle
+        let direction = Vec3::new(1.0, -0.3, 0.0).normalize();
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        assert!(refraction(&direction, &normal, 1.5).is_none());
+        assert!(refraction(&direction, &normal, 1.0 / 1.5).is_some());
+    }
 }
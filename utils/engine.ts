@@ -23,6 +23,7 @@ class Engine {
     canvas: HTMLCanvasElement;
     height: number;
     deltaTime: number;
+    mouseSensitivity: number;
     inputInfo: InputInfo;
     raytracer: Raytracer;
     refreshRate: number;
@@ -40,6 +41,7 @@ class Engine {
         this.refreshRate = 60;
         this.isDestroyed = false;
         this.deltaTime = 0;
+        this.mouseSensitivity = 0.002;
         this.setFps = setFps;
         this.setInputInfo = setInputInfo;
         this.update();
@@ -51,6 +53,16 @@ class Engine {
         addEventListener("keyup", (event) => {
             this.handleEvent(event.key, Event.Up);
         });
+
+        // look around with the mouse once the canvas is clicked
+        canvas.addEventListener("click", () => {
+            canvas.requestPointerLock();
+        });
+
+        addEventListener("mousemove", (event) => {
+            if (this.isDestroyed || document.pointerLockElement !== this.canvas) return;
+            this.raytracer.mouse_look(event.movementX, event.movementY, this.mouseSensitivity);
+        });
     }
 
     public static create(raytracer: Raytracer, canvas: HTMLCanvasElement, width: number, height: number, setFps: Dispatch<SetStateAction<number>>, setInputInfo: Dispatch<SetStateAction<Nullable<InputInfo>>>) {